@@ -42,10 +42,10 @@ const StealthNotes: React.FC = () => {
     setIsLoading(true);
     setResult(null);
     try {
-      const { signature, noteId } = await createStealthNote(numAmount, secret);
+      const { signature, noteId, claimCode } = await createStealthNote(numAmount, secret);
       setResult({
         type: 'success',
-        message: `Stealth note created! Share this secret with the recipient: "${secret}"`,
+        message: `Stealth note created! Share this claim code with the recipient: "${claimCode}"`,
         signature,
         noteId,
       });
@@ -85,7 +85,7 @@ const StealthNotes: React.FC = () => {
           });
         }
       } else {
-        setResult({ type: 'error', message: 'No stealth note found for this secret.' });
+        setResult({ type: 'error', message: 'No stealth note found for this claim code.' });
       }
     } catch (err) {
       setResult({
@@ -177,11 +177,11 @@ const StealthNotes: React.FC = () => {
           <div className="text-xs text-zinc-400">
             {activeTab === 'send' ? (
               <>
-                <strong className="text-amber-400">How it works:</strong> Create a secret passphrase and share it with the recipient off-chain (via message, email, etc.). The recipient's wallet address is <span className="text-green-400">never visible on-chain</span>.
+                <strong className="text-amber-400">How it works:</strong> Create a secret passphrase, then share the resulting claim code with the recipient off-chain (via message, email, etc.). The recipient's wallet address is <span className="text-green-400">never visible on-chain</span>.
               </>
             ) : (
               <>
-                <strong className="text-amber-400">How to claim:</strong> Enter the secret passphrase shared by the sender. If valid, the funds will be added to your encrypted vault balance.
+                <strong className="text-amber-400">How to claim:</strong> Enter the claim code shared by the sender. If valid, the funds will be added to your encrypted vault balance.
               </>
             )}
           </div>
@@ -263,12 +263,12 @@ const StealthNotes: React.FC = () => {
           >
             {/* Secret Input */}
             <div>
-              <label className="block text-xs text-zinc-500 mb-2">Secret Passphrase</label>
+              <label className="block text-xs text-zinc-500 mb-2">Claim Code</label>
               <input
                 type="text"
                 value={secret}
                 onChange={(e) => setSecret(e.target.value)}
-                placeholder="Enter the secret shared by sender"
+                placeholder="Enter the claim code shared by sender"
                 className="w-full bg-zinc-800/50 border border-zinc-700/50 rounded-lg px-4 py-3 text-white placeholder-zinc-600 focus:outline-none focus:border-amber-500/50 transition-colors font-mono"
                 disabled={!isConnected || isLoading}
               />
@@ -121,7 +121,7 @@ type VaultContextValue = {
   fetchUserYieldIndex: () => Promise<string>;
   claimAccess: () => Promise<string>;
   claimYield: () => Promise<string>;
-  createStealthNote: (amount: number, secret: string) => Promise<{ signature: string; noteId: string }>;
+  createStealthNote: (amount: number, secret: string) => Promise<{ signature: string; noteId: string; claimCode: string }>;
   claimStealthNote: (secret: string) => Promise<string>;
   checkStealthNote: (secret: string) => Promise<StealthNoteInfo | null>;
   showWalletModal: boolean;
@@ -586,7 +586,7 @@ export const VaultProvider: React.FC<{ children: ReactNode }> = ({
     }
   }, []);
 
-  const createStealthNote = useCallback(async (amount: number, secret: string): Promise<{ signature: string; noteId: string }> => {
+  const createStealthNote = useCallback(async (amount: number, secret: string): Promise<{ signature: string; noteId: string; claimCode: string }> => {
     dispatch({ type: 'START_OPERATION', payload: 'deposit' });
     try {
       const result = await vaultServiceRef.current.createStealthNote(amount, secret);
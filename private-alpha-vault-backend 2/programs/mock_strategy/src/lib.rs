@@ -0,0 +1,178 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+declare_id!("4XTozP3ZSTtZNDeQweK1GN5P8bAYhfDqus8FZYPj2aUj");
+
+/// Seed prefix for the mock strategy's state PDA
+pub const MOCK_STRATEGY_SEED: &[u8] = b"mock_strategy";
+
+/// Minimal strategy for local testing of the vault's strategy interface.
+/// Allocated SOL simply sits in the state PDA; sending lamports to that
+/// account directly simulates yield, and `set_withdraw_shortfall` makes
+/// withdrawals return less than asked.
+#[program]
+pub mod mock_strategy {
+    use super::*;
+
+    /// Create the state account that will hold `vault`'s allocation.
+    /// `signer` is the vault program's strategy signer PDA for this program;
+    /// only it may call the strategy interface.
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        let state = &mut ctx.accounts.state;
+        state.vault = ctx.accounts.vault.key();
+        state.signer = ctx.accounts.signer.key();
+        state.deposited = 0;
+        state.withdraw_shortfall = 0;
+        state.bump = ctx.bumps.state;
+        Ok(())
+    }
+
+    /// The vault has already credited `lamports` to the state account.
+    pub fn deposit(ctx: Context<Deposit>, lamports: u64) -> Result<()> {
+        let state = &mut ctx.accounts.state;
+        state.deposited = state.deposited.saturating_add(lamports);
+        Ok(())
+    }
+
+    /// Return `lamports` (less any configured shortfall) from the state
+    /// account to the vault.
+    pub fn withdraw(ctx: Context<Withdraw>, lamports: u64) -> Result<()> {
+        require!(lamports <= value_of(&ctx.accounts.state)?, MockStrategyError::InsufficientValue);
+
+        let paid = lamports.saturating_sub(ctx.accounts.state.withdraw_shortfall);
+        ctx.accounts.state.sub_lamports(paid)?;
+        ctx.accounts.vault.add_lamports(paid)?;
+
+        let state = &mut ctx.accounts.state;
+        state.deposited = state.deposited.saturating_sub(lamports);
+        Ok(())
+    }
+
+    /// Report the allocation's value (state lamports above rent) as return data.
+    pub fn report_value(ctx: Context<ReportValue>) -> Result<()> {
+        let value = value_of(&ctx.accounts.state)?;
+        set_return_data(&value.to_le_bytes());
+        Ok(())
+    }
+
+    /// Test hook: make every later withdrawal pay `lamports` less than asked.
+    pub fn set_withdraw_shortfall(ctx: Context<SetWithdrawShortfall>, lamports: u64) -> Result<()> {
+        ctx.accounts.state.withdraw_shortfall = lamports;
+        Ok(())
+    }
+}
+
+fn value_of(state: &Account<MockStrategyState>) -> Result<u64> {
+    let rent = Rent::get()?.minimum_balance(MockStrategyState::SIZE);
+    Ok(state.to_account_info().lamports().saturating_sub(rent))
+}
+
+#[account]
+pub struct MockStrategyState {
+    /// Vault PDA this strategy serves
+    pub vault: Pubkey,
+    /// Vault program's strategy signer PDA, the only caller allowed
+    pub signer: Pubkey,
+    /// Lamports the vault has deposited, net of withdrawals
+    pub deposited: u64,
+    /// Lamports each withdrawal holds back (test hook)
+    pub withdraw_shortfall: u64,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl MockStrategyState {
+    pub const SIZE: usize = 8 + // discriminator
+        32 +  // vault
+        32 +  // signer
+        8 +   // deposited
+        8 +   // withdraw_shortfall
+        1;    // bump
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = payer,
+        seeds = [MOCK_STRATEGY_SEED, vault.key().as_ref()],
+        bump,
+        space = MockStrategyState::SIZE
+    )]
+    pub state: Account<'info, MockStrategyState>,
+
+    /// CHECK: Vault PDA, only its key is recorded
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: Strategy signer PDA, only its key is recorded
+    pub signer: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    pub signer: Signer<'info>,
+
+    /// CHECK: Vault PDA, matched against the state
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [MOCK_STRATEGY_SEED, vault.key().as_ref()],
+        bump = state.bump,
+        has_one = vault,
+        has_one = signer
+    )]
+    pub state: Account<'info, MockStrategyState>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    pub signer: Signer<'info>,
+
+    /// CHECK: Vault PDA, matched against the state
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [MOCK_STRATEGY_SEED, vault.key().as_ref()],
+        bump = state.bump,
+        has_one = vault,
+        has_one = signer
+    )]
+    pub state: Account<'info, MockStrategyState>,
+}
+
+#[derive(Accounts)]
+pub struct ReportValue<'info> {
+    pub signer: Signer<'info>,
+
+    /// CHECK: Vault PDA, matched against the state
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [MOCK_STRATEGY_SEED, vault.key().as_ref()],
+        bump = state.bump,
+        has_one = vault,
+        has_one = signer
+    )]
+    pub state: Account<'info, MockStrategyState>,
+}
+
+#[derive(Accounts)]
+pub struct SetWithdrawShortfall<'info> {
+    #[account(mut)]
+    pub state: Account<'info, MockStrategyState>,
+}
+
+#[error_code]
+pub enum MockStrategyError {
+    #[msg("Withdrawal exceeds the strategy's value")]
+    InsufficientValue,
+}
@@ -4,5 +4,101 @@ pub const VAULT_SEED: &[u8] = b"vault_v2";
 /// Seed prefix for user position PDAs
 pub const USER_SEED: &[u8] = b"user_v2";
 
+/// Seed prefix for vault-owned native stake account PDAs
+pub const STAKE_ACCOUNT_SEED: &[u8] = b"stake_account";
+
+/// Seed prefix for `VaultStake` record PDAs
+pub const VAULT_STAKE_SEED: &[u8] = b"vault_stake";
+
+/// Upper bound on stake target plus strategy weights, so some escrow always stays liquid
+pub const MAX_DEPLOYED_BPS: u16 = 9_000;
+
+/// Upper bound on `Vault::performance_fee_bps`
+pub const MAX_PERFORMANCE_FEE_BPS: u16 = 3_000;
+
+/// Upper bound on any deposit, withdrawal or transfer fee
+pub const MAX_ENTRY_EXIT_FEE_BPS: u16 = 1_000;
+
+/// Length of `Vault::fee_tiers`
+pub const MAX_FEE_TIERS: usize = 4;
+
+/// Vesting period for yield booked by permissionless cranks (stake and
+/// strategy harvests, donations swept by `sync_position`)
+pub const HARVEST_VESTING_PERIOD: i64 = 24 * 60 * 60;
+
+/// Seed for the yield history ring buffer PDA
+pub const YIELD_HISTORY_SEED: &[u8] = b"yield_history";
+
+/// Number of distributions kept in `YieldHistory`
+pub const YIELD_HISTORY_LEN: usize = 128;
+
+/// Seed prefix for yield strategy registry PDAs
+pub const STRATEGY_SEED: &[u8] = b"strategy";
+
+/// Seed prefix for the PDA that signs CPIs into a strategy program.
+/// One per strategy program, so a strategy never sees the vault PDA as signer.
+pub const STRATEGY_SIGNER_SEED: &[u8] = b"strategy_signer";
+
 /// Seed prefix for stealth note PDAs
 pub const STEALTH_NOTE_SEED: &[u8] = b"stealth_note";
+
+/// Domain tag for salted note ids (`StealthNote::id_version` 1)
+pub const NOTE_ID_DOMAIN: &[u8] = b"private_alpha_vault:note_id:v1";
+
+/// Current note id scheme; 0 is the legacy plain `hash(secret)`
+pub const NOTE_ID_VERSION: u8 = 1;
+
+/// Seed prefix for stealth meta-address registry PDAs
+pub const STEALTH_META_SEED: &[u8] = b"stealth_meta";
+
+/// Seed prefix for SPL token note PDAs
+pub const TOKEN_NOTE_SEED: &[u8] = b"token_note";
+
+/// Seed prefix for note batch PDAs
+pub const NOTE_BATCH_SEED: &[u8] = b"note_batch";
+
+/// Domain tag for note batch Merkle leaves
+pub const NOTE_BATCH_LEAF_DOMAIN: &[u8] = b"private_alpha_vault:note_batch_leaf:v1";
+
+/// Maximum number of notes in a single batch (bitmap stays at 1 KiB)
+pub const MAX_NOTE_BATCH_LEAVES: u32 = 8192;
+
+/// Seed prefix for split-note claim receipts
+pub const NOTE_CLAIM_SEED: &[u8] = b"note_claim";
+
+/// Domain tag prefixed to the message signed for a relayed note claim
+pub const RELAYED_CLAIM_DOMAIN: &[u8] = b"private_alpha_vault:relayed_claim:v1";
+
+/// Domain tag prefixed to the message signed for a split note claim
+pub const NOTE_SHARE_CLAIM_DOMAIN: &[u8] = b"private_alpha_vault:note_share_claim:v1";
+
+/// Domain tag prefixed to the message signed for a partial note claim
+pub const NOTE_PARTIAL_CLAIM_DOMAIN: &[u8] = b"private_alpha_vault:note_partial_claim:v1";
+
+/// Seed prefix for payment stream PDAs
+pub const STREAM_SEED: &[u8] = b"stream";
+
+/// Seed prefix for scheduled transfer PDAs
+pub const SCHEDULED_TRANSFER_SEED: &[u8] = b"scheduled_transfer";
+
+/// Seed prefix for arbiter escrow PDAs
+pub const ESCROW_SEED: &[u8] = b"escrow";
+
+/// Seed prefix for subscription PDAs
+pub const SUBSCRIPTION_SEED: &[u8] = b"subscription";
+
+/// Maximum size of an encrypted memo in bytes
+pub const MAX_MEMO_LEN: usize = 256;
+
+/// Maximum number of recipients in a single batch transfer
+pub const MAX_BATCH_RECIPIENTS: usize = 8;
+
+/// Maximum number of positions settled by one `settle_positions` call
+pub const MAX_SETTLE_POSITIONS: usize = 16;
+
+/// Upper bound on `Vault::crank_reward_lamports`
+pub const MAX_CRANK_REWARD_LAMPORTS: u64 = 100_000;
+
+/// Share of the yield `settle_positions` settles (basis points) paid to the
+/// cranker, up to `Vault::crank_reward_lamports` per position
+pub const CRANK_REWARD_BPS: u16 = 100;
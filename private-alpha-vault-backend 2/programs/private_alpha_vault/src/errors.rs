@@ -20,4 +20,110 @@ pub enum VaultError {
     InvalidSecret,
     #[msg("Stealth note has already been claimed")]
     NoteAlreadyClaimed,
+    #[msg("Batch must contain between 1 and MAX_BATCH_RECIPIENTS entries with matching accounts")]
+    InvalidBatchSize,
+    #[msg("Account is not the expected user position PDA")]
+    InvalidPositionAccount,
+    #[msg("Recipient appears more than once in the batch")]
+    DuplicateRecipient,
+    #[msg("Stream schedule is invalid")]
+    InvalidStreamSchedule,
+    #[msg("Nothing has accrued to withdraw yet")]
+    NothingToWithdraw,
+    #[msg("Sender and recipient must be different")]
+    SelfTransfer,
+    #[msg("Time lock must be in the future")]
+    InvalidTimeLock,
+    #[msg("Scheduled transfer cannot execute yet")]
+    TransferNotYetExecutable,
+    #[msg("Scheduled transfer is already executable and can no longer be cancelled")]
+    TransferAlreadyExecutable,
+    #[msg("Escrow must be resolved by the arbiter or by sender and recipient together")]
+    EscrowUnauthorized,
+    #[msg("Escrow timeout has not passed yet")]
+    EscrowNotExpired,
+    #[msg("Subscription period must be positive")]
+    InvalidSubscriptionPeriod,
+    #[msg("Subscription charge is not due yet")]
+    SubscriptionNotDue,
+    #[msg("Subscription has reached its maximum number of charges")]
+    SubscriptionExhausted,
+    #[msg("Position already uses the current layout")]
+    PositionAlreadyMigrated,
+    #[msg("Instruction does not match the note's claim mode")]
+    WrongClaimMode,
+    #[msg("Invalid claim mode parameters")]
+    InvalidClaimMode,
+    #[msg("Claim amount must be positive and at most the note's remaining lamports")]
+    InvalidClaimAmount,
+    #[msg("Stealth note already uses the current layout")]
+    NoteAlreadyMigrated,
+    #[msg("Account is not a stealth note")]
+    InvalidNoteAccount,
+    #[msg("Relayed claims are not enabled for this note")]
+    RelayedClaimDisabled,
+    #[msg("Missing or invalid ed25519 signature")]
+    InvalidSignature,
+    #[msg("Relayer fee exceeds the note amount")]
+    RelayerFeeTooHigh,
+    #[msg("Stealth note is not revocable or its revocation window has passed")]
+    NoteNotRevocable,
+    #[msg("Note batch size or denomination is invalid")]
+    InvalidNoteBatch,
+    #[msg("Merkle proof does not match the note batch")]
+    InvalidMerkleProof,
+    #[msg("This note of the batch has already been claimed")]
+    BatchNoteAlreadyClaimed,
+    #[msg("Memo must be non-empty and at most MAX_MEMO_LEN bytes")]
+    InvalidMemo,
+    #[msg("Amount must be greater than zero")]
+    InvalidAmount,
+    #[msg("Stake target plus strategy weights exceed MAX_DEPLOYED_BPS")]
+    InvalidStakeTarget,
+    #[msg("Delegation would exceed the vault's stake target")]
+    StakeTargetExceeded,
+    #[msg("Rewards for this stake account were already harvested this epoch")]
+    AlreadyHarvested,
+    #[msg("Stake account has not been deactivated")]
+    StakeNotDeactivated,
+    #[msg("Stake account has already been deactivated")]
+    StakeAlreadyDeactivated,
+    #[msg("Vault already uses the current layout")]
+    VaultAlreadyMigrated,
+    #[msg("Allocation would exceed the strategy's target weight")]
+    StrategyWeightExceeded,
+    #[msg("Strategy did not move the expected lamports")]
+    StrategyTransferMismatch,
+    #[msg("Strategy did not return a value")]
+    MissingStrategyValue,
+    #[msg("Vesting period cannot be negative")]
+    InvalidVestingPeriod,
+    #[msg("Escrow plus owed and vesting yield exceeds the vault pool")]
+    YieldInvariantViolated,
+    #[msg("Performance fee exceeds MAX_PERFORMANCE_FEE_BPS")]
+    InvalidFee,
+    #[msg("No fees to collect")]
+    NoFeesAccrued,
+    #[msg("Fee schedule has too many tiers, unsorted tiers or a fee above MAX_ENTRY_EXIT_FEE_BPS")]
+    InvalidFeeSchedule,
+    #[msg("Position principal is locked until its unlock time")]
+    PositionLocked,
+    #[msg("Unknown lock tier")]
+    InvalidLockTier,
+    #[msg("New lock would end before the current one")]
+    LockShortened,
+    #[msg("Position is not locked")]
+    PositionNotLocked,
+    #[msg("Pass between 1 and MAX_SETTLE_POSITIONS user positions")]
+    InvalidSettleBatch,
+    #[msg("Crank reward exceeds MAX_CRANK_REWARD_LAMPORTS")]
+    InvalidCrankReward,
+    #[msg("Escrow arbiter must be neither the sender nor the recipient")]
+    InvalidArbiter,
+    #[msg("Split and partial notes need a claim authority")]
+    ClaimAuthorityRequired,
+    #[msg("Note batch has expired")]
+    NoteBatchExpired,
+    #[msg("Note batch has not expired yet")]
+    NoteBatchNotExpired,
 }
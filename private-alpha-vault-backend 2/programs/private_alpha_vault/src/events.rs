@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::state::FeeKind;
+
+/// Client-encrypted memo attached to a payment or note.
+/// The program never sees the plaintext; it only bounds the size.
+#[event]
+pub struct EncryptedMemo {
+    /// Wallet that attached the memo
+    pub sender: Pubkey,
+    /// Intended reader, or the default pubkey for stealth notes
+    pub recipient: Pubkey,
+    /// Account the memo belongs to (recipient position, note, stream, ...)
+    pub reference: Pubkey,
+    /// Ciphertext, encrypted client-side to the recipient
+    pub memo: Vec<u8>,
+}
+
+/// Performance fee taken from a yield distribution.
+#[event]
+pub struct PerformanceFeeAccrued {
+    /// Yield before the fee (lamports)
+    pub gross_lamports: u64,
+    /// Fee kept for the protocol (lamports)
+    pub fee_lamports: u64,
+    /// Fees accrued and not yet collected, including this one
+    pub accrued_fees: u64,
+}
+
+/// Deposit, withdrawal or transfer fee charged.
+#[event]
+pub struct EntryExitFeeCharged {
+    /// Operation the fee was charged on
+    pub kind: FeeKind,
+    /// Wallet that paid it
+    pub payer: Pubkey,
+    /// Amount before the fee (lamports); `None` for transfers, whose amount
+    /// is kept out of the logs
+    pub gross_lamports: Option<u64>,
+    /// Fee (lamports)
+    pub fee_lamports: u64,
+}
+
+/// Accrued fees paid out of the pool.
+#[event]
+pub struct FeesCollected {
+    /// Wallet the fees were paid to
+    pub fee_recipient: Pubkey,
+    /// Lamports paid
+    pub lamports: u64,
+}
+
+/// Principal lost by a stake account or strategy, spread over all positions.
+#[event]
+pub struct LossSocialised {
+    /// Principal that did not come back (lamports)
+    pub lamports: u64,
+    /// Part covered by yield that had not vested yet (lamports)
+    pub absorbed_by_vesting: u64,
+    /// `loss_index` after the loss was booked
+    pub loss_index: u128,
+}
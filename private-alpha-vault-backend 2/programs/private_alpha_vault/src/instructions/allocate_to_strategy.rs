@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use crate::state::{Strategy, Vault};
+use crate::constants::{STRATEGY_SEED, STRATEGY_SIGNER_SEED, VAULT_SEED};
+use crate::errors::VaultError;
+use crate::instructions::strategy_utils::{strategy_deposit, StrategyAccounts};
+
+/// Move `lamports` of pooled escrow into a strategy, up to its target weight
+/// of total escrow. Any accounts the strategy's `deposit` needs beyond the
+/// fixed strategy accounts are passed as remaining accounts.
+#[derive(Accounts)]
+pub struct AllocateToStrategy<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [STRATEGY_SEED, strategy_program.key().as_ref()],
+        bump = strategy.bump,
+        has_one = strategy_program,
+        has_one = strategy_state
+    )]
+    pub strategy: Account<'info, Strategy>,
+
+    /// CHECK: Strategy program matched against the registry
+    pub strategy_program: UncheckedAccount<'info>,
+
+    /// CHECK: Strategy state account matched against the registry
+    #[account(mut)]
+    pub strategy_state: UncheckedAccount<'info>,
+
+    /// CHECK: PDA that signs CPIs into this strategy program; holds nothing
+    #[account(seeds = [STRATEGY_SIGNER_SEED, strategy_program.key().as_ref()], bump)]
+    pub strategy_signer: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AllocateToStrategy<'info>>,
+    lamports: u64,
+) -> Result<()> {
+    require!(lamports > 0, VaultError::InvalidAmount);
+
+    let deployed_after = ctx
+        .accounts
+        .strategy
+        .deployed_lamports
+        .checked_add(lamports)
+        .ok_or(VaultError::Overflow)?;
+    require!(
+        deployed_after <= ctx.accounts.strategy.allocation_cap(ctx.accounts.vault.total_escrow_lamports),
+        VaultError::StrategyWeightExceeded
+    );
+
+    let strategy_program = ctx.accounts.strategy_program.to_account_info();
+    let strategy_state = ctx.accounts.strategy_state.to_account_info();
+    let strategy_signer = ctx.accounts.strategy_signer.to_account_info();
+    strategy_deposit(
+        &ctx.accounts.vault,
+        &StrategyAccounts {
+            program: &strategy_program,
+            state: &strategy_state,
+            signer: &strategy_signer,
+            signer_bump: ctx.bumps.strategy_signer,
+            extra: ctx.remaining_accounts,
+        },
+        lamports,
+    )?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.strategy_lamports = vault
+        .strategy_lamports
+        .checked_add(lamports)
+        .ok_or(VaultError::Overflow)?;
+    ctx.accounts.strategy.deployed_lamports = deployed_after;
+
+    msg!("Allocated {} lamports to strategy {}", lamports, ctx.accounts.strategy.strategy_program);
+
+    Ok(())
+}
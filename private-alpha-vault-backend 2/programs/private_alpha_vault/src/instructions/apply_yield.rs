@@ -1,12 +1,15 @@
 use anchor_lang::prelude::*;
 use inco_lightning::cpi::accounts::Operation;
-use inco_lightning::cpi::{e_add, new_euint128};
+use inco_lightning::cpi::new_euint128;
 use inco_lightning::ID as INCO_LIGHTNING_ID;
-use crate::state::Vault;
+use crate::state::{Vault, YieldHistory};
+use crate::constants::YIELD_HISTORY_SEED;
+use crate::instructions::yield_utils::distribute_yield;
 use anchor_lang::solana_program::system_instruction;
 
-const YIELD_INDEX_SCALE: u128 = 1_000_000_000_000;
-
+/// Pay `lamports` of yield into the pool. It vests into `yield_index`
+/// linearly over `vesting_period` seconds, so escrow that arrives just before
+/// and leaves just after only earns the part that dripped while it was in.
 #[derive(Accounts)]
 pub struct ApplyYield<'info> {
     #[account(
@@ -25,9 +28,21 @@ pub struct ApplyYield<'info> {
     /// CHECK: Inco Lightning program for encrypted operations
     #[account(address = INCO_LIGHTNING_ID)]
     pub inco_lightning_program: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [YIELD_HISTORY_SEED],
+        bump
+    )]
+    pub yield_history: AccountLoader<'info, YieldHistory>,
 }
 
-pub fn handler(ctx: Context<ApplyYield>, encrypted_yield: Vec<u8>, lamports: u64) -> Result<()> {
+pub fn handler(
+    ctx: Context<ApplyYield>,
+    encrypted_yield: Vec<u8>,
+    lamports: u64,
+    vesting_period: i64,
+) -> Result<()> {
     let inco_program = ctx.accounts.inco_lightning_program.to_account_info();
     let signer = ctx.accounts.authority.to_account_info();
 
@@ -49,16 +64,15 @@ pub fn handler(ctx: Context<ApplyYield>, encrypted_yield: Vec<u8>, lamports: u64
     let cpi_ctx = CpiContext::new(inco_program.clone(), Operation { signer: signer.clone() });
     let yield_handle = new_euint128(cpi_ctx, encrypted_yield, 0)?;
 
-    // Add yield to vault's total encrypted balance
-    let cpi_ctx = CpiContext::new(inco_program, Operation { signer });
-    let new_balance = e_add(cpi_ctx, ctx.accounts.vault.total_encrypted_balance, yield_handle, 0)?;
-    ctx.accounts.vault.total_encrypted_balance = new_balance;
-
-    // Update yield index (fixed-point): delta = yield / total_escrow
-    let delta_index = (lamports as u128)
-        .saturating_mul(YIELD_INDEX_SCALE)
-        / (ctx.accounts.vault.total_escrow_lamports as u128);
-    ctx.accounts.vault.yield_index = ctx.accounts.vault.yield_index.saturating_add(delta_index);
+    distribute_yield(
+        &mut ctx.accounts.vault,
+        &ctx.accounts.yield_history,
+        lamports,
+        yield_handle,
+        vesting_period,
+        inco_program,
+        signer,
+    )?;
 
     Ok(())
 }
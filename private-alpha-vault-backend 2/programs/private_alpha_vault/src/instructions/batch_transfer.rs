@@ -0,0 +1,163 @@
+use anchor_lang::prelude::*;
+use inco_lightning::cpi::accounts::Operation;
+use inco_lightning::cpi::new_euint128;
+use inco_lightning::types::Euint128;
+use inco_lightning::ID as INCO_LIGHTNING_ID;
+use crate::state::{FeeKind, UserPosition, Vault};
+use crate::constants::{MAX_BATCH_RECIPIENTS, USER_SEED, VAULT_SEED};
+use crate::errors::VaultError;
+use crate::instructions::fee_utils::{charge_position_fee, net_of_fee, take_fee};
+use crate::instructions::memo_utils::post_memo;
+use crate::instructions::position_utils::{load_or_init_user_position, move_between_positions};
+use crate::instructions::yield_utils::apply_pending_yield;
+
+/// One leg of a batch transfer. Recipients are matched to entries by position
+/// in `remaining_accounts`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BatchTransferEntry {
+    /// Client-encrypted amount for this recipient
+    pub encrypted_amount: Vec<u8>,
+    /// Principal lamports credited to this recipient
+    pub lamports: u64,
+    /// Optional memo encrypted client-side to this recipient
+    pub memo: Option<Vec<u8>>,
+}
+
+/// Transfer to many recipients in one instruction.
+/// The sender's pending yield is settled once and each recipient's before it
+/// is credited; the whole batch fails atomically.
+#[derive(Accounts)]
+pub struct BatchTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [USER_SEED, sender.key().as_ref()],
+        bump = sender_position.bump,
+        constraint = sender_position.owner == sender.key() @ VaultError::Unauthorized
+    )]
+    pub sender_position: Account<'info, UserPosition>,
+
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Inco Lightning program for encrypted operations
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: AccountInfo<'info>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BatchTransfer<'info>>,
+    entries: Vec<BatchTransferEntry>,
+) -> Result<()> {
+    require!(
+        !entries.is_empty() && entries.len() <= MAX_BATCH_RECIPIENTS,
+        VaultError::InvalidBatchSize
+    );
+    // remaining_accounts, per entry i:
+    // [2i]     recipient (readonly)
+    // [2i + 1] recipient_position (mut) - created if missing, paid by sender
+    require!(
+        ctx.remaining_accounts.len() == entries.len() * 2,
+        VaultError::InvalidBatchSize
+    );
+
+    let inco_program = ctx.accounts.inco_lightning_program.to_account_info();
+    let signer = ctx.accounts.sender.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let sender_key = ctx.accounts.sender.key();
+
+    // Settle the sender's yield once for the whole batch.
+    apply_pending_yield(
+        &mut ctx.accounts.vault,
+        &mut ctx.accounts.sender_position,
+        inco_program.clone(),
+        signer.clone(),
+        system_program.clone(),
+    )?;
+
+    let mut total_lamports: u64 = 0;
+    for entry in entries.iter() {
+        total_lamports = total_lamports
+            .checked_add(entry.lamports)
+            .ok_or(VaultError::Overflow)?;
+    }
+
+    if ctx.accounts.sender_position.escrow_lamports < total_lamports {
+        return Err(VaultError::InsufficientEscrow.into());
+    }
+
+    let mut seen: Vec<Pubkey> = Vec::with_capacity(entries.len());
+    let mut total_fee: u64 = 0;
+    for (i, entry) in entries.into_iter().enumerate() {
+        let recipient = &ctx.remaining_accounts[i * 2];
+        let recipient_position_info = &ctx.remaining_accounts[i * 2 + 1];
+        let recipient_key = recipient.key();
+
+        // The sender position is already loaded; a duplicate would clobber its data on exit.
+        require_keys_neq!(recipient_key, sender_key, VaultError::InvalidPositionAccount);
+        require!(!seen.contains(&recipient_key), VaultError::DuplicateRecipient);
+        seen.push(recipient_key);
+
+        let mut recipient_position = load_or_init_user_position(
+            recipient_position_info,
+            &recipient_key,
+            &ctx.accounts.vault,
+            signer.clone(),
+            inco_program.clone(),
+            system_program.clone(),
+        )?;
+        apply_pending_yield(
+            &mut ctx.accounts.vault,
+            &mut recipient_position,
+            inco_program.clone(),
+            signer.clone(),
+            system_program.clone(),
+        )?;
+
+        // Convert ciphertext -> encrypted handle (input_type = 0), net of the transfer fee
+        let fee = take_fee(&mut ctx.accounts.vault, FeeKind::Transfer, sender_key, entry.lamports)?;
+        let cpi_ctx = CpiContext::new(inco_program.clone(), Operation { signer: signer.clone() });
+        let gross_handle: Euint128 = new_euint128(cpi_ctx, entry.encrypted_amount, 0)?;
+        let amount_handle = net_of_fee(gross_handle, fee, inco_program.clone(), signer.clone())?;
+        total_fee += fee;
+
+        // Move encrypted balance and principal; escrow stays pooled in the vault.
+        move_between_positions(
+            &mut ctx.accounts.vault,
+            &mut ctx.accounts.sender_position,
+            &mut recipient_position,
+            amount_handle,
+            entry.lamports - fee,
+            inco_program.clone(),
+            signer.clone(),
+        )?;
+
+        post_memo(entry.memo, sender_key, recipient_key, recipient_position_info.key())?;
+
+        recipient_position.exit(&crate::ID)?;
+    }
+
+    // Transfer fees for the whole batch come out of the sender in one go.
+    charge_position_fee(
+        &mut ctx.accounts.vault,
+        &mut ctx.accounts.sender_position,
+        total_fee,
+        inco_program,
+        signer,
+    )?;
+
+    // NOTE: No auto-authorize here since remaining_accounts carry the recipients.
+    // Sender and recipients call claimAccess() to get decrypt permissions.
+
+    msg!("Batch transfer to {} recipients", seen.len());
+
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use inco_lightning::ID as INCO_LIGHTNING_ID;
+use crate::state::{ScheduledTransfer, UserPosition, Vault};
+use crate::constants::{SCHEDULED_TRANSFER_SEED, USER_SEED, VAULT_SEED};
+use crate::errors::VaultError;
+use crate::instructions::position_utils::credit_position;
+use crate::instructions::yield_utils::apply_pending_yield;
+
+/// Cancel a scheduled transfer before it becomes executable, returning the
+/// escrowed amount to the sender's position.
+#[derive(Accounts)]
+pub struct CancelScheduledTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [
+            SCHEDULED_TRANSFER_SEED,
+            sender.key().as_ref(),
+            &scheduled_transfer.transfer_id.to_le_bytes(),
+        ],
+        bump = scheduled_transfer.bump,
+        has_one = sender @ VaultError::Unauthorized,
+        close = sender
+    )]
+    pub scheduled_transfer: Account<'info, ScheduledTransfer>,
+
+    #[account(
+        mut,
+        seeds = [USER_SEED, sender.key().as_ref()],
+        bump = sender_position.bump,
+        constraint = sender_position.owner == sender.key() @ VaultError::Unauthorized
+    )]
+    pub sender_position: Account<'info, UserPosition>,
+
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Inco Lightning program for encrypted operations
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: AccountInfo<'info>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CancelScheduledTransfer<'info>>,
+) -> Result<()> {
+    let inco_program = ctx.accounts.inco_lightning_program.to_account_info();
+    let signer = ctx.accounts.sender.to_account_info();
+    let clock = Clock::get()?;
+
+    require!(
+        !ctx.accounts.scheduled_transfer.execute_after.is_unlocked(&clock),
+        VaultError::TransferAlreadyExecutable
+    );
+
+    apply_pending_yield(
+        &mut ctx.accounts.vault,
+        &mut ctx.accounts.sender_position,
+        inco_program.clone(),
+        signer.clone(),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    let amount_handle = ctx.accounts.scheduled_transfer.encrypted_amount;
+    let lamports = ctx.accounts.scheduled_transfer.lamports;
+    let scheduled_info = ctx.accounts.scheduled_transfer.to_account_info();
+    credit_position(
+        &mut ctx.accounts.vault,
+        &mut ctx.accounts.sender_position,
+        &scheduled_info,
+        amount_handle,
+        lamports,
+        inco_program,
+        signer,
+    )?;
+
+    msg!("Scheduled transfer {} cancelled", ctx.accounts.scheduled_transfer.transfer_id);
+
+    Ok(())
+}
@@ -0,0 +1,145 @@
+use anchor_lang::prelude::*;
+use inco_lightning::cpi::accounts::Operation;
+use inco_lightning::cpi::as_euint128;
+use inco_lightning::ID as INCO_LIGHTNING_ID;
+use crate::state::{FeeKind, Stream, UserPosition, Vault};
+use crate::constants::{STREAM_SEED, USER_SEED, VAULT_SEED};
+use crate::errors::VaultError;
+use crate::instructions::fee_utils::credit_position_net_of_fee;
+use crate::instructions::position_utils::{credit_position, init_position_if_new};
+use crate::instructions::yield_utils::apply_pending_yield;
+
+/// Stop a stream early. Whatever has accrued but not been withdrawn goes to the
+/// recipient's position net of the transfer fee, the unaccrued remainder
+/// returns to the sender's position, and the stream account is closed to the
+/// sender.
+#[derive(Accounts)]
+pub struct CancelStream<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [
+            STREAM_SEED,
+            stream.sender.as_ref(),
+            stream.recipient.as_ref(),
+            &stream.stream_id.to_le_bytes(),
+        ],
+        bump = stream.bump,
+        has_one = sender @ VaultError::Unauthorized,
+        has_one = recipient @ VaultError::Unauthorized,
+        close = sender
+    )]
+    pub stream: Account<'info, Stream>,
+
+    #[account(
+        mut,
+        seeds = [USER_SEED, sender.key().as_ref()],
+        bump = sender_position.bump,
+        constraint = sender_position.owner == sender.key() @ VaultError::Unauthorized
+    )]
+    pub sender_position: Account<'info, UserPosition>,
+
+    #[account(
+        init_if_needed,
+        payer = sender,
+        seeds = [USER_SEED, recipient.key().as_ref()],
+        bump,
+        space = UserPosition::SIZE
+    )]
+    pub recipient_position: Account<'info, UserPosition>,
+
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    /// CHECK: Recipient address, matched against the stream
+    pub recipient: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Inco Lightning program for encrypted operations
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: AccountInfo<'info>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, CancelStream<'info>>) -> Result<()> {
+    let inco_program = ctx.accounts.inco_lightning_program.to_account_info();
+    let signer = ctx.accounts.sender.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let clock = Clock::get()?;
+
+    let stream = &ctx.accounts.stream;
+    let accrued = stream.accrued_at(clock.unix_timestamp);
+    let to_recipient = accrued.saturating_sub(stream.withdrawn_lamports);
+    let to_sender = stream.deposited_lamports.saturating_sub(accrued);
+    let stream_info = ctx.accounts.stream.to_account_info();
+
+    init_position_if_new(
+        &mut ctx.accounts.recipient_position,
+        ctx.accounts.recipient.key(),
+        ctx.bumps.recipient_position,
+        &ctx.accounts.vault,
+        inco_program.clone(),
+        signer.clone(),
+    )?;
+
+    if to_recipient > 0 {
+        apply_pending_yield(
+            &mut ctx.accounts.vault,
+            &mut ctx.accounts.recipient_position,
+            inco_program.clone(),
+            signer.clone(),
+            system_program.clone(),
+        )?;
+
+        let cpi_ctx = CpiContext::new(inco_program.clone(), Operation { signer: signer.clone() });
+        let amount_handle = as_euint128(cpi_ctx, to_recipient as u128)?;
+        credit_position_net_of_fee(
+            &mut ctx.accounts.vault,
+            &mut ctx.accounts.recipient_position,
+            &stream_info,
+            amount_handle,
+            to_recipient,
+            Some(FeeKind::Transfer),
+            ctx.accounts.stream.sender,
+            inco_program.clone(),
+            signer.clone(),
+        )?;
+    }
+
+    if to_sender > 0 {
+        apply_pending_yield(
+            &mut ctx.accounts.vault,
+            &mut ctx.accounts.sender_position,
+            inco_program.clone(),
+            signer.clone(),
+            system_program,
+        )?;
+
+        let cpi_ctx = CpiContext::new(inco_program.clone(), Operation { signer: signer.clone() });
+        let amount_handle = as_euint128(cpi_ctx, to_sender as u128)?;
+        credit_position(
+            &mut ctx.accounts.vault,
+            &mut ctx.accounts.sender_position,
+            &stream_info,
+            amount_handle,
+            to_sender,
+            inco_program,
+            signer,
+        )?;
+    }
+
+    msg!(
+        "Stream {} cancelled: {} to recipient, {} refunded",
+        ctx.accounts.stream.stream_id,
+        to_recipient,
+        to_sender
+    );
+
+    Ok(())
+}
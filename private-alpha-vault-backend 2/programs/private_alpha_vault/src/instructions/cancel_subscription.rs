@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::state::Subscription;
+use crate::constants::SUBSCRIPTION_SEED;
+use crate::errors::VaultError;
+
+/// Cancel a subscription. Only the payer can call this; rent returns to them.
+#[derive(Accounts)]
+pub struct CancelSubscription<'info> {
+    #[account(
+        mut,
+        seeds = [
+            SUBSCRIPTION_SEED,
+            payer.key().as_ref(),
+            subscription.merchant.as_ref(),
+            &subscription.subscription_id.to_le_bytes(),
+        ],
+        bump = subscription.bump,
+        has_one = payer @ VaultError::Unauthorized,
+        close = payer
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CancelSubscription>) -> Result<()> {
+    msg!("Subscription {} cancelled", ctx.accounts.subscription.subscription_id);
+    Ok(())
+}
@@ -0,0 +1,143 @@
+use anchor_lang::prelude::*;
+use inco_lightning::ID as INCO_LIGHTNING_ID;
+use crate::state::{FeeKind, Subscription, UserPosition, Vault};
+use crate::constants::{SUBSCRIPTION_SEED, USER_SEED, VAULT_SEED};
+use crate::errors::VaultError;
+use crate::instructions::fee_utils::{charge_position_fee, net_of_fee, take_fee};
+use crate::instructions::position_utils::{init_position_if_new, move_between_positions};
+use crate::instructions::yield_utils::apply_pending_yield;
+
+/// Charge one due period of a subscription. Permissionless so the merchant
+/// (or any crank) can run it; the charger pays rent for a new merchant position.
+/// Like `transfer`, the payer is debited the full amount and the merchant is
+/// credited net of the transfer fee.
+#[derive(Accounts)]
+pub struct ChargeSubscription<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [
+            SUBSCRIPTION_SEED,
+            subscription.payer.as_ref(),
+            merchant.key().as_ref(),
+            &subscription.subscription_id.to_le_bytes(),
+        ],
+        bump = subscription.bump,
+        has_one = merchant @ VaultError::Unauthorized
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    #[account(
+        mut,
+        seeds = [USER_SEED, subscription.payer.as_ref()],
+        bump = payer_position.bump
+    )]
+    pub payer_position: Account<'info, UserPosition>,
+
+    #[account(
+        init_if_needed,
+        payer = charger,
+        seeds = [USER_SEED, merchant.key().as_ref()],
+        bump,
+        space = UserPosition::SIZE
+    )]
+    pub merchant_position: Account<'info, UserPosition>,
+
+    #[account(mut)]
+    pub charger: Signer<'info>,
+
+    /// CHECK: Merchant address, matched against the subscription
+    pub merchant: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Inco Lightning program for encrypted operations
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: AccountInfo<'info>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ChargeSubscription<'info>>) -> Result<()> {
+    let inco_program = ctx.accounts.inco_lightning_program.to_account_info();
+    let signer = ctx.accounts.charger.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let clock = Clock::get()?;
+
+    let subscription = &ctx.accounts.subscription;
+    require!(!subscription.is_exhausted(), VaultError::SubscriptionExhausted);
+    require!(
+        clock.unix_timestamp >= subscription.next_due,
+        VaultError::SubscriptionNotDue
+    );
+    let amount_handle = subscription.encrypted_amount;
+    let lamports = subscription.lamports;
+    let payer = subscription.payer;
+
+    init_position_if_new(
+        &mut ctx.accounts.merchant_position,
+        ctx.accounts.merchant.key(),
+        ctx.bumps.merchant_position,
+        &ctx.accounts.vault,
+        inco_program.clone(),
+        signer.clone(),
+    )?;
+
+    // Settle both sides' pending yield before the balances change.
+    apply_pending_yield(
+        &mut ctx.accounts.vault,
+        &mut ctx.accounts.payer_position,
+        inco_program.clone(),
+        signer.clone(),
+        system_program.clone(),
+    )?;
+    apply_pending_yield(
+        &mut ctx.accounts.vault,
+        &mut ctx.accounts.merchant_position,
+        inco_program.clone(),
+        signer.clone(),
+        system_program,
+    )?;
+
+    let fee = take_fee(&mut ctx.accounts.vault, FeeKind::Transfer, payer, lamports)?;
+    let net_handle = net_of_fee(amount_handle, fee, inco_program.clone(), signer.clone())?;
+    move_between_positions(
+        &mut ctx.accounts.vault,
+        &mut ctx.accounts.payer_position,
+        &mut ctx.accounts.merchant_position,
+        net_handle,
+        lamports - fee,
+        inco_program.clone(),
+        signer.clone(),
+    )?;
+    charge_position_fee(
+        &mut ctx.accounts.vault,
+        &mut ctx.accounts.payer_position,
+        fee,
+        inco_program,
+        signer,
+    )?;
+
+    // One period per call; a crank catches up on missed periods by calling again.
+    let subscription = &mut ctx.accounts.subscription;
+    subscription.next_due = subscription
+        .next_due
+        .checked_add(subscription.period_seconds)
+        .ok_or(VaultError::Overflow)?;
+    subscription.periods_charged = subscription
+        .periods_charged
+        .checked_add(1)
+        .ok_or(VaultError::Overflow)?;
+
+    msg!(
+        "Subscription {} charged (period {})",
+        subscription.subscription_id,
+        subscription.periods_charged
+    );
+
+    Ok(())
+}
@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use inco_lightning::ID as INCO_LIGHTNING_ID;
+use crate::state::{derive_note_id, FeeKind, NoteBatch, UserPosition, Vault};
+use crate::constants::{NOTE_BATCH_SEED, USER_SEED, VAULT_SEED};
+use crate::errors::VaultError;
+use crate::instructions::fee_utils::credit_position_net_of_fee;
+use crate::instructions::position_utils::init_position_if_new;
+use crate::instructions::yield_utils::apply_pending_yield;
+
+/// Claim one note of a batch by revealing its secret and a Merkle proof that
+/// its note id is leaf `leaf_index`. The denomination goes to the claimer's position.
+#[derive(Accounts)]
+pub struct ClaimBatchNote<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [NOTE_BATCH_SEED, note_batch.sender.as_ref(), &note_batch.batch_id.to_le_bytes()],
+        bump = note_batch.bump
+    )]
+    pub note_batch: Account<'info, NoteBatch>,
+
+    #[account(
+        init_if_needed,
+        payer = claimer,
+        seeds = [USER_SEED, claimer.key().as_ref()],
+        bump,
+        space = UserPosition::SIZE
+    )]
+    pub claimer_position: Account<'info, UserPosition>,
+
+    #[account(mut)]
+    pub claimer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Inco Lightning program for encrypted operations
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: AccountInfo<'info>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ClaimBatchNote<'info>>,
+    leaf_index: u32,
+    secret: Vec<u8>,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let inco_program = ctx.accounts.inco_lightning_program.to_account_info();
+    let signer = ctx.accounts.claimer.to_account_info();
+
+    let note_batch = &ctx.accounts.note_batch;
+    require!(
+        !note_batch.expires_at.is_unlocked(&Clock::get()?),
+        VaultError::NoteBatchExpired
+    );
+    require!(leaf_index < note_batch.leaf_count, VaultError::InvalidMerkleProof);
+    require!(!note_batch.is_claimed(leaf_index), VaultError::BatchNoteAlreadyClaimed);
+    let note_id = derive_note_id(&ctx.accounts.vault.key(), &note_batch.salt, &secret);
+    require!(
+        note_batch.verify_leaf(leaf_index, &note_id, &proof),
+        VaultError::InvalidMerkleProof
+    );
+
+    init_position_if_new(
+        &mut ctx.accounts.claimer_position,
+        ctx.accounts.claimer.key(),
+        ctx.bumps.claimer_position,
+        &ctx.accounts.vault,
+        inco_program.clone(),
+        signer.clone(),
+    )?;
+
+    // Apply any pending yield before adding claimed funds
+    apply_pending_yield(
+        &mut ctx.accounts.vault,
+        &mut ctx.accounts.claimer_position,
+        inco_program.clone(),
+        signer.clone(),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    let amount_handle = ctx.accounts.note_batch.encrypted_denomination;
+    let lamports = ctx.accounts.note_batch.denomination_lamports;
+    // Batches are funded from the sender's wallet, so the claim is a deposit.
+    let batch_info = ctx.accounts.note_batch.to_account_info();
+    credit_position_net_of_fee(
+        &mut ctx.accounts.vault,
+        &mut ctx.accounts.claimer_position,
+        &batch_info,
+        amount_handle,
+        lamports,
+        Some(FeeKind::Deposit),
+        ctx.accounts.note_batch.sender,
+        inco_program,
+        signer,
+    )?;
+
+    let note_batch = &mut ctx.accounts.note_batch;
+    note_batch.set_claimed(leaf_index);
+    note_batch.claimed_count += 1;
+
+    msg!(
+        "Batch note {} claimed ({}/{})",
+        leaf_index,
+        note_batch.claimed_count,
+        note_batch.leaf_count
+    );
+
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::ID as INSTRUCTIONS_SYSVAR_ID;
+use inco_lightning::ID as INCO_LIGHTNING_ID;
+use crate::state::{NoteClaimMode, StealthNote, UserPosition, Vault};
+use crate::constants::{STEALTH_NOTE_SEED, USER_SEED, VAULT_SEED};
+use crate::errors::VaultError;
+use crate::instructions::note_utils::{take_from_note, verify_partial_claim};
+use crate::instructions::fee_utils::credit_position_net_of_fee;
+use crate::instructions::position_utils::init_position_if_new;
+use crate::instructions::yield_utils::apply_pending_yield;
+
+/// Withdraw part of a partial-claim stealth note into the claimer's position.
+/// The note keeps the remaining lamports and an updated encrypted remainder.
+/// Authorized by the note's `claim_authority` signing `partial_claim_message`
+/// for this claimer, amount and the note's claim count in a preceding Ed25519
+/// instruction, so a signature cannot be replayed or used for another amount.
+#[derive(Accounts)]
+pub struct ClaimNotePartial<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [STEALTH_NOTE_SEED, stealth_note.note_id.as_ref()],
+        bump = stealth_note.bump,
+        constraint = !stealth_note.claimed @ VaultError::NoteAlreadyClaimed,
+        constraint = stealth_note.claim_mode == NoteClaimMode::Partial @ VaultError::WrongClaimMode,
+        constraint = stealth_note.claim_authority != Pubkey::default() @ VaultError::ClaimAuthorityRequired
+    )]
+    pub stealth_note: Account<'info, StealthNote>,
+
+    #[account(
+        init_if_needed,
+        payer = claimer,
+        seeds = [USER_SEED, claimer.key().as_ref()],
+        bump,
+        space = UserPosition::SIZE
+    )]
+    pub claimer_position: Account<'info, UserPosition>,
+
+    #[account(mut)]
+    pub claimer: Signer<'info>,
+
+    /// CHECK: Instructions sysvar, used to read the preceding Ed25519 instruction
+    #[account(address = INSTRUCTIONS_SYSVAR_ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Inco Lightning program for encrypted operations
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: AccountInfo<'info>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ClaimNotePartial<'info>>,
+    lamports: u64,
+) -> Result<()> {
+    let inco_program = ctx.accounts.inco_lightning_program.to_account_info();
+    let signer = ctx.accounts.claimer.to_account_info();
+
+    verify_partial_claim(
+        &ctx.accounts.instructions_sysvar,
+        &ctx.accounts.stealth_note,
+        &ctx.accounts.claimer.key(),
+        lamports,
+    )?;
+
+    init_position_if_new(
+        &mut ctx.accounts.claimer_position,
+        ctx.accounts.claimer.key(),
+        ctx.bumps.claimer_position,
+        &ctx.accounts.vault,
+        inco_program.clone(),
+        signer.clone(),
+    )?;
+
+    // Apply any pending yield before adding claimed funds
+    apply_pending_yield(
+        &mut ctx.accounts.vault,
+        &mut ctx.accounts.claimer_position,
+        inco_program.clone(),
+        signer.clone(),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    let amount_handle = take_from_note(
+        &mut ctx.accounts.stealth_note,
+        lamports,
+        inco_program.clone(),
+        signer.clone(),
+    )?;
+
+    let note_info = ctx.accounts.stealth_note.to_account_info();
+    credit_position_net_of_fee(
+        &mut ctx.accounts.vault,
+        &mut ctx.accounts.claimer_position,
+        &note_info,
+        amount_handle,
+        lamports,
+        ctx.accounts.stealth_note.payout_fee_kind(true),
+        ctx.accounts.stealth_note.sender,
+        inco_program,
+        signer,
+    )?;
+
+    msg!(
+        "Stealth note partially claimed: {} lamports left",
+        ctx.accounts.stealth_note.lamports
+    );
+
+    Ok(())
+}
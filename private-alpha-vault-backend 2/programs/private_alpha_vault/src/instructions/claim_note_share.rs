@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::ID as INSTRUCTIONS_SYSVAR_ID;
+use inco_lightning::ID as INCO_LIGHTNING_ID;
+use crate::state::{NoteClaimMode, NoteClaimReceipt, StealthNote, UserPosition, Vault};
+use crate::constants::{NOTE_CLAIM_SEED, STEALTH_NOTE_SEED, USER_SEED, VAULT_SEED};
+use crate::errors::VaultError;
+use crate::instructions::note_utils::{take_from_note, verify_share_claim};
+use crate::instructions::fee_utils::credit_position_net_of_fee;
+use crate::instructions::position_utils::init_position_if_new;
+use crate::instructions::yield_utils::apply_pending_yield;
+
+/// Claim one equal share of a split stealth note (red-packet style).
+/// Authorized by an Ed25519 instruction placed right before this one, signed
+/// by the note's `claim_authority` over `share_claim_message` for this
+/// claimer, so nothing reusable is revealed on-chain. A receipt PDA per
+/// (note, claimer) stops the same wallet claiming twice.
+#[derive(Accounts)]
+pub struct ClaimNoteShare<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [STEALTH_NOTE_SEED, stealth_note.note_id.as_ref()],
+        bump = stealth_note.bump,
+        constraint = !stealth_note.claimed @ VaultError::NoteAlreadyClaimed,
+        constraint = stealth_note.claim_authority != Pubkey::default() @ VaultError::ClaimAuthorityRequired
+    )]
+    pub stealth_note: Account<'info, StealthNote>,
+
+    #[account(
+        init,
+        payer = claimer,
+        seeds = [NOTE_CLAIM_SEED, stealth_note.key().as_ref(), claimer.key().as_ref()],
+        bump,
+        space = NoteClaimReceipt::SIZE
+    )]
+    pub claim_receipt: Account<'info, NoteClaimReceipt>,
+
+    #[account(
+        init_if_needed,
+        payer = claimer,
+        seeds = [USER_SEED, claimer.key().as_ref()],
+        bump,
+        space = UserPosition::SIZE
+    )]
+    pub claimer_position: Account<'info, UserPosition>,
+
+    #[account(mut)]
+    pub claimer: Signer<'info>,
+
+    /// CHECK: Instructions sysvar, used to read the preceding Ed25519 instruction
+    #[account(address = INSTRUCTIONS_SYSVAR_ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Inco Lightning program for encrypted operations
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: AccountInfo<'info>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ClaimNoteShare<'info>>,
+) -> Result<()> {
+    let inco_program = ctx.accounts.inco_lightning_program.to_account_info();
+    let signer = ctx.accounts.claimer.to_account_info();
+    let clock = Clock::get()?;
+
+    verify_share_claim(
+        &ctx.accounts.instructions_sysvar,
+        &ctx.accounts.stealth_note,
+        &ctx.accounts.claimer.key(),
+    )?;
+    let max_claims = match ctx.accounts.stealth_note.claim_mode {
+        NoteClaimMode::Split { max_claims } => max_claims,
+        _ => return Err(VaultError::WrongClaimMode.into()),
+    };
+
+    init_position_if_new(
+        &mut ctx.accounts.claimer_position,
+        ctx.accounts.claimer.key(),
+        ctx.bumps.claimer_position,
+        &ctx.accounts.vault,
+        inco_program.clone(),
+        signer.clone(),
+    )?;
+
+    // Apply any pending yield before adding claimed funds
+    apply_pending_yield(
+        &mut ctx.accounts.vault,
+        &mut ctx.accounts.claimer_position,
+        inco_program.clone(),
+        signer.clone(),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    let share = ctx.accounts.stealth_note.next_split_share(max_claims);
+    let share_handle = take_from_note(
+        &mut ctx.accounts.stealth_note,
+        share,
+        inco_program.clone(),
+        signer.clone(),
+    )?;
+
+    let note_info = ctx.accounts.stealth_note.to_account_info();
+    credit_position_net_of_fee(
+        &mut ctx.accounts.vault,
+        &mut ctx.accounts.claimer_position,
+        &note_info,
+        share_handle,
+        share,
+        ctx.accounts.stealth_note.payout_fee_kind(true),
+        ctx.accounts.stealth_note.sender,
+        inco_program,
+        signer,
+    )?;
+
+    let receipt = &mut ctx.accounts.claim_receipt;
+    receipt.note = ctx.accounts.stealth_note.key();
+    receipt.claimer = ctx.accounts.claimer.key();
+    receipt.lamports = share;
+    receipt.claimed_at = clock.unix_timestamp;
+    receipt.bump = ctx.bumps.claim_receipt;
+
+    msg!(
+        "Stealth note share {}/{} claimed",
+        ctx.accounts.stealth_note.claims_made,
+        max_claims
+    );
+
+    Ok(())
+}
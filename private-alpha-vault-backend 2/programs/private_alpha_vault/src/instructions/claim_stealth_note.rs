@@ -1,17 +1,18 @@
 use anchor_lang::prelude::*;
 use inco_lightning::cpi::accounts::{Allow, Operation};
-use inco_lightning::cpi::{allow, as_euint128, e_add};
+use inco_lightning::cpi::{allow, as_euint128};
 use inco_lightning::ID as INCO_LIGHTNING_ID;
-use crate::state::{StealthNote, UserPosition, Vault};
+use crate::state::{NoteClaimMode, StealthNote, UserPosition, Vault};
 use crate::constants::{STEALTH_NOTE_SEED, USER_SEED, VAULT_SEED};
 use crate::errors::VaultError;
+use crate::instructions::fee_utils::credit_position_net_of_fee;
 use crate::instructions::yield_utils::apply_pending_yield;
 
 /// Claim a stealth note by proving knowledge of the secret.
-/// The claimer provides the raw secret, which is hashed to derive the note_id.
-/// If the hash matches, the note's funds are transferred to the claimer's position.
+/// The claimer provides the raw secret, which is checked against the note_id
+/// under the note's id scheme (see `StealthNote::verify_secret`).
+/// If it matches, the note's funds are transferred to the claimer's position.
 #[derive(Accounts)]
-#[instruction(secret: Vec<u8>)]
 pub struct ClaimStealthNote<'info> {
     #[account(
         mut,
@@ -22,10 +23,10 @@ pub struct ClaimStealthNote<'info> {
 
     #[account(
         mut,
-        // The note_id is derived from hashing the secret
-        seeds = [STEALTH_NOTE_SEED, &anchor_lang::solana_program::hash::hash(&secret).to_bytes()],
+        seeds = [STEALTH_NOTE_SEED, stealth_note.note_id.as_ref()],
         bump = stealth_note.bump,
-        constraint = !stealth_note.claimed @ VaultError::NoteAlreadyClaimed
+        constraint = !stealth_note.claimed @ VaultError::NoteAlreadyClaimed,
+        constraint = stealth_note.claim_mode == NoteClaimMode::Single @ VaultError::WrongClaimMode
     )]
     pub stealth_note: Account<'info, StealthNote>,
 
@@ -57,9 +58,11 @@ pub fn handler<'info>(
     let stealth_note = &mut ctx.accounts.stealth_note;
     let claimer_position = &mut ctx.accounts.claimer_position;
 
-    // Verify the secret hashes to the note_id (already enforced by PDA seeds, but double-check)
-    let computed_hash = anchor_lang::solana_program::hash::hash(&secret).to_bytes();
-    require!(computed_hash == stealth_note.note_id, VaultError::InvalidSecret);
+    // Verify the secret derives the note_id
+    require!(
+        stealth_note.verify_secret(&ctx.accounts.vault.key(), &secret),
+        VaultError::InvalidSecret
+    );
 
     // Initialize claimer position if new
     if claimer_position.owner == Pubkey::default() {
@@ -77,39 +80,21 @@ pub fn handler<'info>(
         ctx.accounts.system_program.to_account_info(),
     )?;
 
-    // Transfer SOL from stealth note PDA to claimer's position PDA
-    // Use manual lamport adjustment since stealth_note carries data
+    // Move SOL from the stealth note PDA into the vault pool as the claimer's
+    // principal, net of the fee for where the note was funded from.
     let lamports = stealth_note.lamports;
-    **stealth_note.to_account_info().try_borrow_mut_lamports()? -= lamports;
-    **claimer_position.to_account_info().try_borrow_mut_lamports()? += lamports;
-
-    // Update vault's total escrow (adding the claimed amount)
-    ctx.accounts.vault.total_escrow_lamports = ctx
-        .accounts
-        .vault
-        .total_escrow_lamports
-        .checked_add(lamports)
-        .ok_or(VaultError::Overflow)?;
-
-    // Add the encrypted amount to claimer's balance
-    let cpi_ctx = CpiContext::new(inco_program.clone(), Operation { signer: signer.clone() });
-    let new_claimer_balance = e_add(
-        cpi_ctx,
-        claimer_position.encrypted_balance,
-        stealth_note.encrypted_amount,
-        0,
-    )?;
-    claimer_position.encrypted_balance = new_claimer_balance;
-
-    // Add to vault's total encrypted balance
-    let cpi_ctx = CpiContext::new(inco_program.clone(), Operation { signer: signer.clone() });
-    let new_vault_balance = e_add(
-        cpi_ctx,
-        ctx.accounts.vault.total_encrypted_balance,
+    let new_claimer_balance = credit_position_net_of_fee(
+        &mut ctx.accounts.vault,
+        claimer_position,
+        &stealth_note.to_account_info(),
         stealth_note.encrypted_amount,
-        0,
+        lamports,
+        stealth_note.payout_fee_kind(true),
+        stealth_note.sender,
+        inco_program.clone(),
+        signer.clone(),
     )?;
-    ctx.accounts.vault.total_encrypted_balance = new_vault_balance;
+    let new_vault_balance = ctx.accounts.vault.total_encrypted_balance;
 
     // Set owner and bump if first interaction
     claimer_position.owner = ctx.accounts.claimer.key();
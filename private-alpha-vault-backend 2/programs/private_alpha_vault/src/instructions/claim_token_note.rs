@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{
+    close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+    TransferChecked,
+};
+use crate::state::TokenNote;
+use crate::constants::{TOKEN_NOTE_SEED, VAULT_SEED};
+use crate::errors::VaultError;
+
+/// Claim a token note by revealing its secret. The tokens go to the claimer's
+/// associated token account (created if missing, paid by the claimer); the
+/// note's token account and the note itself are closed back to the sender.
+#[derive(Accounts)]
+pub struct ClaimTokenNote<'info> {
+    /// CHECK: Vault PDA; only its address is used, for the salted note id
+    #[account(seeds = [VAULT_SEED], bump)]
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [TOKEN_NOTE_SEED, token_note.note_id.as_ref()],
+        bump = token_note.bump,
+        has_one = mint,
+        has_one = sender,
+        constraint = token_note.token_program == token_program.key() @ VaultError::InvalidNoteAccount,
+        constraint = !token_note.claimed @ VaultError::NoteAlreadyClaimed,
+        close = sender
+    )]
+    pub token_note: Account<'info, TokenNote>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = token_note,
+        associated_token::token_program = token_program
+    )]
+    pub note_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = claimer,
+        associated_token::mint = mint,
+        associated_token::authority = claimer,
+        associated_token::token_program = token_program
+    )]
+    pub claimer_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub claimer: Signer<'info>,
+
+    /// CHECK: Note sender, receives the rent of the closed accounts
+    #[account(mut)]
+    pub sender: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ClaimTokenNote>, secret: Vec<u8>) -> Result<()> {
+    let token_note = &ctx.accounts.token_note;
+    require!(
+        token_note.verify_secret(&ctx.accounts.vault.key(), &secret),
+        VaultError::InvalidSecret
+    );
+
+    let note_id = token_note.note_id;
+    let bump_seed = [token_note.bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[TOKEN_NOTE_SEED, note_id.as_ref(), &bump_seed]];
+
+    // Pay out everything the account holds, including anything sent to it after creation.
+    let amount = ctx.accounts.note_token_account.amount;
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.note_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.claimer_token_account.to_account_info(),
+            authority: ctx.accounts.token_note.to_account_info(),
+        },
+        signer_seeds,
+    );
+    transfer_checked(cpi_ctx, amount, ctx.accounts.mint.decimals)?;
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.note_token_account.to_account_info(),
+            destination: ctx.accounts.sender.to_account_info(),
+            authority: ctx.accounts.token_note.to_account_info(),
+        },
+        signer_seeds,
+    );
+    close_account(cpi_ctx)?;
+
+    msg!("Token note claimed: {} tokens", amount);
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::state::Vault;
+use crate::constants::VAULT_SEED;
+use crate::errors::VaultError;
+use crate::events::FeesCollected;
+use crate::instructions::position_utils::release_from_pool;
+
+/// Pay all accrued performance fees from the pool to the fee recipient.
+/// Fees are paid from liquid lamports only, like withdrawals.
+#[derive(Accounts)]
+pub struct CollectFees<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump,
+        has_one = fee_recipient @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(mut)]
+    pub fee_recipient: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CollectFees>) -> Result<()> {
+    let lamports = ctx.accounts.vault.accrued_fees;
+    require!(lamports > 0, VaultError::NoFeesAccrued);
+
+    release_from_pool(
+        &ctx.accounts.vault,
+        &ctx.accounts.fee_recipient.to_account_info(),
+        lamports,
+    )?;
+    ctx.accounts.vault.accrued_fees = 0;
+
+    emit!(FeesCollected {
+        fee_recipient: ctx.accounts.fee_recipient.key(),
+        lamports,
+    });
+
+    Ok(())
+}
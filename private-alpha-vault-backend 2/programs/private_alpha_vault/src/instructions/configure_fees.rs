@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use crate::state::Vault;
+use crate::constants::{MAX_PERFORMANCE_FEE_BPS, VAULT_SEED};
+use crate::errors::VaultError;
+
+/// Set the performance fee taken from each yield distribution and the wallet
+/// that may collect it. Fees already accrued go to the new recipient.
+#[derive(Accounts)]
+pub struct ConfigureFees<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ConfigureFees>, performance_fee_bps: u16, fee_recipient: Pubkey) -> Result<()> {
+    require!(performance_fee_bps <= MAX_PERFORMANCE_FEE_BPS, VaultError::InvalidFee);
+
+    let vault = &mut ctx.accounts.vault;
+    vault.performance_fee_bps = performance_fee_bps;
+    vault.fee_recipient = fee_recipient;
+
+    msg!("Performance fee set to {} bps for {}", performance_fee_bps, fee_recipient);
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::state::Vault;
+use crate::constants::{MAX_DEPLOYED_BPS, VAULT_SEED};
+use crate::errors::VaultError;
+
+/// Set the share of pooled escrow that may be delegated to stake accounts.
+/// Lowering it does not undelegate anything; the authority deactivates
+/// stake accounts to restore liquidity.
+#[derive(Accounts)]
+pub struct ConfigureStake<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ConfigureStake>, stake_target_bps: u16) -> Result<()> {
+    require!(
+        stake_target_bps as u32 + ctx.accounts.vault.strategy_weight_bps as u32
+            <= MAX_DEPLOYED_BPS as u32,
+        VaultError::InvalidStakeTarget
+    );
+    ctx.accounts.vault.stake_target_bps = stake_target_bps;
+
+    msg!("Stake target set to {} bps", stake_target_bps);
+
+    Ok(())
+}
@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+use inco_lightning::cpi::accounts::Operation;
+use inco_lightning::cpi::new_euint128;
+use inco_lightning::types::Euint128;
+use inco_lightning::ID as INCO_LIGHTNING_ID;
+use crate::state::{Escrow, TimeLock, UserPosition, Vault};
+use crate::constants::{ESCROW_SEED, USER_SEED, VAULT_SEED};
+use crate::errors::VaultError;
+use crate::instructions::memo_utils::post_memo;
+use crate::instructions::position_utils::debit_position;
+use crate::instructions::yield_utils::apply_pending_yield;
+
+/// Lock funds from the sender's position into an arbiter-mediated escrow.
+#[derive(Accounts)]
+#[instruction(escrow_id: u64)]
+pub struct CreateEscrow<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [USER_SEED, sender.key().as_ref()],
+        bump = sender_position.bump,
+        constraint = sender_position.owner == sender.key() @ VaultError::Unauthorized
+    )]
+    pub sender_position: Account<'info, UserPosition>,
+
+    #[account(
+        init,
+        payer = sender,
+        seeds = [ESCROW_SEED, sender.key().as_ref(), &escrow_id.to_le_bytes()],
+        bump,
+        space = Escrow::SIZE
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    /// CHECK: Recipient address
+    #[account(constraint = recipient.key() != sender.key() @ VaultError::SelfTransfer)]
+    pub recipient: UncheckedAccount<'info>,
+
+    /// CHECK: Arbiter address; must be a third party
+    #[account(
+        constraint = arbiter.key() != sender.key() && arbiter.key() != recipient.key()
+            @ VaultError::InvalidArbiter
+    )]
+    pub arbiter: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Inco Lightning program for encrypted operations
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: AccountInfo<'info>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CreateEscrow<'info>>,
+    escrow_id: u64,
+    encrypted_amount: Vec<u8>,
+    lamports: u64,
+    reclaimable_after: TimeLock,
+    memo: Option<Vec<u8>>,
+) -> Result<()> {
+    let inco_program = ctx.accounts.inco_lightning_program.to_account_info();
+    let signer = ctx.accounts.sender.to_account_info();
+    let clock = Clock::get()?;
+
+    require!(!reclaimable_after.is_unlocked(&clock), VaultError::InvalidTimeLock);
+
+    // Apply any pending yield before moving escrow out of the position.
+    apply_pending_yield(
+        &mut ctx.accounts.vault,
+        &mut ctx.accounts.sender_position,
+        inco_program.clone(),
+        signer.clone(),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    // Convert ciphertext -> encrypted handle (input_type = 0)
+    let cpi_ctx = CpiContext::new(inco_program.clone(), Operation { signer: signer.clone() });
+    let amount_handle: Euint128 = new_euint128(cpi_ctx, encrypted_amount, 0)?;
+
+    let escrow_info = ctx.accounts.escrow.to_account_info();
+    debit_position(
+        &mut ctx.accounts.vault,
+        &mut ctx.accounts.sender_position,
+        &escrow_info,
+        amount_handle,
+        lamports,
+        inco_program,
+        signer,
+    )?;
+
+    let escrow = &mut ctx.accounts.escrow;
+    escrow.sender = ctx.accounts.sender.key();
+    escrow.recipient = ctx.accounts.recipient.key();
+    escrow.arbiter = ctx.accounts.arbiter.key();
+    escrow.escrow_id = escrow_id;
+    escrow.encrypted_amount = amount_handle;
+    escrow.lamports = lamports;
+    escrow.created_at = clock.unix_timestamp;
+    escrow.reclaimable_after = reclaimable_after;
+    escrow.bump = ctx.bumps.escrow;
+
+    post_memo(
+        memo,
+        ctx.accounts.sender.key(),
+        ctx.accounts.recipient.key(),
+        ctx.accounts.escrow.key(),
+    )?;
+
+    msg!("Escrow {} opened", escrow_id);
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use inco_lightning::cpi::accounts::Operation;
+use inco_lightning::cpi::new_euint128;
+use inco_lightning::types::Euint128;
+use inco_lightning::ID as INCO_LIGHTNING_ID;
+use crate::state::{NoteBatch, TimeLock};
+use crate::constants::{MAX_NOTE_BATCH_LEAVES, NOTE_BATCH_SEED};
+use crate::errors::VaultError;
+
+/// Issue `leaf_count` stealth notes of the same denomination in one instruction.
+/// The sender commits to the note ids with a Merkle root (see `NoteBatch`) and
+/// funds `leaf_count * denomination_lamports` from their wallet up front.
+/// Notes left unclaimed when `expires_at` passes can be reclaimed by the
+/// sender with `reclaim_note_batch`.
+#[derive(Accounts)]
+#[instruction(batch_id: u64, merkle_root: [u8; 32], leaf_count: u32)]
+pub struct CreateNoteBatch<'info> {
+    #[account(
+        init,
+        payer = sender,
+        seeds = [NOTE_BATCH_SEED, sender.key().as_ref(), &batch_id.to_le_bytes()],
+        bump,
+        space = NoteBatch::size(leaf_count)
+    )]
+    pub note_batch: Account<'info, NoteBatch>,
+
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Inco Lightning program for encrypted operations
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: AccountInfo<'info>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CreateNoteBatch<'info>>,
+    batch_id: u64,
+    merkle_root: [u8; 32],
+    leaf_count: u32,
+    salt: [u8; 32],
+    encrypted_denomination: Vec<u8>,
+    denomination_lamports: u64,
+    expires_at: TimeLock,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(
+        leaf_count > 0
+            && leaf_count <= MAX_NOTE_BATCH_LEAVES
+            && denomination_lamports > 0
+            && !expires_at.is_unlocked(&clock),
+        VaultError::InvalidNoteBatch
+    );
+    let total_lamports = denomination_lamports
+        .checked_mul(leaf_count as u64)
+        .ok_or(VaultError::Overflow)?;
+
+    let inco_program = ctx.accounts.inco_lightning_program.to_account_info();
+    let signer = ctx.accounts.sender.to_account_info();
+
+    // Transfer real SOL for every note to the batch PDA
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        anchor_lang::system_program::Transfer {
+            from: ctx.accounts.sender.to_account_info(),
+            to: ctx.accounts.note_batch.to_account_info(),
+        },
+    );
+    anchor_lang::system_program::transfer(cpi_ctx, total_lamports)?;
+
+    // Convert ciphertext -> encrypted handle (input_type = 0)
+    let cpi_ctx = CpiContext::new(inco_program, Operation { signer });
+    let denomination_handle: Euint128 = new_euint128(cpi_ctx, encrypted_denomination, 0)?;
+
+    let note_batch = &mut ctx.accounts.note_batch;
+    note_batch.sender = ctx.accounts.sender.key();
+    note_batch.batch_id = batch_id;
+    note_batch.merkle_root = merkle_root;
+    note_batch.salt = salt;
+    note_batch.encrypted_denomination = denomination_handle;
+    note_batch.denomination_lamports = denomination_lamports;
+    note_batch.leaf_count = leaf_count;
+    note_batch.claimed_count = 0;
+    note_batch.created_at = clock.unix_timestamp;
+    note_batch.bump = ctx.bumps.note_batch;
+    note_batch.expires_at = expires_at;
+    note_batch.claimed_bitmap = vec![0u8; NoteBatch::bitmap_len(leaf_count)];
+
+    msg!("Note batch {} created with {} notes", batch_id, leaf_count);
+
+    Ok(())
+}
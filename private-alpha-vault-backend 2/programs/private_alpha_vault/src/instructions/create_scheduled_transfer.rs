@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use inco_lightning::cpi::accounts::Operation;
+use inco_lightning::cpi::new_euint128;
+use inco_lightning::types::Euint128;
+use inco_lightning::ID as INCO_LIGHTNING_ID;
+use crate::state::{ScheduledTransfer, TimeLock, UserPosition, Vault};
+use crate::constants::{SCHEDULED_TRANSFER_SEED, USER_SEED, VAULT_SEED};
+use crate::errors::VaultError;
+use crate::instructions::memo_utils::post_memo;
+use crate::instructions::position_utils::debit_position;
+use crate::instructions::yield_utils::apply_pending_yield;
+
+/// Queue a transfer that can only execute after `execute_after`.
+/// The amount is debited from the sender's position and escrowed in the PDA now.
+#[derive(Accounts)]
+#[instruction(transfer_id: u64)]
+pub struct CreateScheduledTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [USER_SEED, sender.key().as_ref()],
+        bump = sender_position.bump,
+        constraint = sender_position.owner == sender.key() @ VaultError::Unauthorized
+    )]
+    pub sender_position: Account<'info, UserPosition>,
+
+    #[account(
+        init,
+        payer = sender,
+        seeds = [SCHEDULED_TRANSFER_SEED, sender.key().as_ref(), &transfer_id.to_le_bytes()],
+        bump,
+        space = ScheduledTransfer::SIZE
+    )]
+    pub scheduled_transfer: Account<'info, ScheduledTransfer>,
+
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    /// CHECK: Recipient address
+    #[account(constraint = recipient.key() != sender.key() @ VaultError::SelfTransfer)]
+    pub recipient: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Inco Lightning program for encrypted operations
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: AccountInfo<'info>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CreateScheduledTransfer<'info>>,
+    transfer_id: u64,
+    encrypted_amount: Vec<u8>,
+    lamports: u64,
+    execute_after: TimeLock,
+    memo: Option<Vec<u8>>,
+) -> Result<()> {
+    let inco_program = ctx.accounts.inco_lightning_program.to_account_info();
+    let signer = ctx.accounts.sender.to_account_info();
+    let clock = Clock::get()?;
+
+    require!(!execute_after.is_unlocked(&clock), VaultError::InvalidTimeLock);
+
+    // Apply any pending yield before moving escrow out of the position.
+    apply_pending_yield(
+        &mut ctx.accounts.vault,
+        &mut ctx.accounts.sender_position,
+        inco_program.clone(),
+        signer.clone(),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    // Convert ciphertext -> encrypted handle (input_type = 0)
+    let cpi_ctx = CpiContext::new(inco_program.clone(), Operation { signer: signer.clone() });
+    let amount_handle: Euint128 = new_euint128(cpi_ctx, encrypted_amount, 0)?;
+
+    let scheduled_info = ctx.accounts.scheduled_transfer.to_account_info();
+    debit_position(
+        &mut ctx.accounts.vault,
+        &mut ctx.accounts.sender_position,
+        &scheduled_info,
+        amount_handle,
+        lamports,
+        inco_program,
+        signer,
+    )?;
+
+    let scheduled = &mut ctx.accounts.scheduled_transfer;
+    scheduled.sender = ctx.accounts.sender.key();
+    scheduled.recipient = ctx.accounts.recipient.key();
+    scheduled.transfer_id = transfer_id;
+    scheduled.encrypted_amount = amount_handle;
+    scheduled.lamports = lamports;
+    scheduled.execute_after = execute_after;
+    scheduled.created_at = clock.unix_timestamp;
+    scheduled.bump = ctx.bumps.scheduled_transfer;
+
+    post_memo(
+        memo,
+        ctx.accounts.sender.key(),
+        ctx.accounts.recipient.key(),
+        ctx.accounts.scheduled_transfer.key(),
+    )?;
+
+    msg!("Scheduled transfer {} queued", transfer_id);
+
+    Ok(())
+}
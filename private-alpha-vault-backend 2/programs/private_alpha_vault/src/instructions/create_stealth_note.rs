@@ -5,9 +5,11 @@ use inco_lightning::types::Euint128;
 use inco_lightning::ID as INCO_LIGHTNING_ID;
 use crate::state::StealthNote;
 use crate::constants::STEALTH_NOTE_SEED;
+use crate::instructions::memo_utils::post_memo;
 
 /// Create a stealth note that can be claimed by anyone who knows the secret.
-/// The sender provides a note_id (hash of secret passphrase) and encrypted amount.
+/// The sender provides a note_id (derived from the secret passphrase and
+/// `salt`; see `derive_note_id`) and encrypted amount.
 /// Real SOL is transferred to the note PDA.
 #[derive(Accounts)]
 #[instruction(note_id: [u8; 32], encrypted_amount: Vec<u8>, lamports: u64)]
@@ -36,6 +38,8 @@ pub fn handler<'info>(
     note_id: [u8; 32],
     encrypted_amount: Vec<u8>,
     lamports: u64,
+    salt: [u8; 32],
+    memo: Option<Vec<u8>>,
 ) -> Result<()> {
     let inco_program = ctx.accounts.inco_lightning_program.to_account_info();
     let signer = ctx.accounts.sender.to_account_info();
@@ -60,14 +64,23 @@ pub fn handler<'info>(
     stealth_note.note_id = note_id;
     stealth_note.encrypted_amount = amount_handle;
     stealth_note.lamports = lamports;
+    stealth_note.original_lamports = lamports;
     stealth_note.sender = ctx.accounts.sender.key();
     stealth_note.created_at = clock.unix_timestamp;
     stealth_note.claimed = false;
     stealth_note.bump = ctx.bumps.stealth_note;
+    stealth_note.set_id_scheme(salt);
 
     // Note: We do NOT grant decrypt access here - the claimer will get access when they claim.
     // The sender can optionally grant themselves access if they want to verify.
 
+    post_memo(
+        memo,
+        ctx.accounts.sender.key(),
+        Pubkey::default(),
+        ctx.accounts.stealth_note.key(),
+    )?;
+
     msg!("Stealth note created with ID: {:?}", note_id);
 
     Ok(())
@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+use inco_lightning::cpi::accounts::Operation;
+use inco_lightning::cpi::new_euint128;
+use inco_lightning::types::Euint128;
+use inco_lightning::ID as INCO_LIGHTNING_ID;
+use crate::state::{StealthNote, UserPosition, Vault};
+use crate::constants::{STEALTH_NOTE_SEED, USER_SEED, VAULT_SEED};
+use crate::errors::VaultError;
+use crate::instructions::memo_utils::post_memo;
+use crate::instructions::position_utils::debit_position;
+use crate::instructions::yield_utils::apply_pending_yield;
+
+/// Create a stealth note funded from the sender's vault position instead of
+/// their wallet. The encrypted amount is subtracted from the position and the
+/// lamports move from the vault pool into the note PDA, so no system transfer
+/// from the sender's wallet shows up on-chain.
+#[derive(Accounts)]
+#[instruction(note_id: [u8; 32])]
+pub struct CreateStealthNoteFromPosition<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [USER_SEED, sender.key().as_ref()],
+        bump = sender_position.bump,
+        constraint = sender_position.owner == sender.key() @ VaultError::Unauthorized
+    )]
+    pub sender_position: Account<'info, UserPosition>,
+
+    #[account(
+        init,
+        payer = sender,
+        seeds = [STEALTH_NOTE_SEED, note_id.as_ref()],
+        bump,
+        space = StealthNote::SIZE
+    )]
+    pub stealth_note: Account<'info, StealthNote>,
+
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Inco Lightning program for encrypted operations
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: AccountInfo<'info>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CreateStealthNoteFromPosition<'info>>,
+    note_id: [u8; 32],
+    encrypted_amount: Vec<u8>,
+    lamports: u64,
+    salt: [u8; 32],
+    memo: Option<Vec<u8>>,
+) -> Result<()> {
+    let inco_program = ctx.accounts.inco_lightning_program.to_account_info();
+    let signer = ctx.accounts.sender.to_account_info();
+    let clock = Clock::get()?;
+
+    // Apply any pending yield before moving escrow out of the position.
+    apply_pending_yield(
+        &mut ctx.accounts.vault,
+        &mut ctx.accounts.sender_position,
+        inco_program.clone(),
+        signer.clone(),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    // Convert ciphertext -> encrypted handle (input_type = 0)
+    let cpi_ctx = CpiContext::new(inco_program.clone(), Operation { signer: signer.clone() });
+    let amount_handle: Euint128 = new_euint128(cpi_ctx, encrypted_amount, 0)?;
+
+    let note_info = ctx.accounts.stealth_note.to_account_info();
+    debit_position(
+        &mut ctx.accounts.vault,
+        &mut ctx.accounts.sender_position,
+        &note_info,
+        amount_handle,
+        lamports,
+        inco_program,
+        signer,
+    )?;
+
+    // Initialize the stealth note
+    let stealth_note = &mut ctx.accounts.stealth_note;
+    stealth_note.note_id = note_id;
+    stealth_note.encrypted_amount = amount_handle;
+    stealth_note.lamports = lamports;
+    stealth_note.original_lamports = lamports;
+    stealth_note.sender = ctx.accounts.sender.key();
+    stealth_note.created_at = clock.unix_timestamp;
+    stealth_note.claimed = false;
+    stealth_note.bump = ctx.bumps.stealth_note;
+    stealth_note.set_id_scheme(salt);
+    stealth_note.funded_from_position = true;
+
+    post_memo(
+        memo,
+        ctx.accounts.sender.key(),
+        Pubkey::default(),
+        ctx.accounts.stealth_note.key(),
+    )?;
+
+    msg!("Stealth note created from position with ID: {:?}", note_id);
+
+    Ok(())
+}
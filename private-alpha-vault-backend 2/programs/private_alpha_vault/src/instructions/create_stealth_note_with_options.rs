@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use inco_lightning::cpi::accounts::Operation;
+use inco_lightning::cpi::new_euint128;
+use inco_lightning::types::Euint128;
+use inco_lightning::ID as INCO_LIGHTNING_ID;
+use crate::state::{NoteClaimMode, NoteOptions, StealthNote};
+use crate::constants::STEALTH_NOTE_SEED;
+use crate::errors::VaultError;
+use crate::instructions::memo_utils::post_memo;
+
+/// Create a stealth note with non-default behaviour: split or partial claims,
+/// a claim key for relayed claims, addressing to a stealth meta-address, or
+/// a window in which the sender can revoke it.
+/// Funding works like `create_stealth_note`: real SOL moves from the sender's wallet.
+#[derive(Accounts)]
+#[instruction(note_id: [u8; 32])]
+pub struct CreateStealthNoteWithOptions<'info> {
+    #[account(
+        init,
+        payer = sender,
+        seeds = [STEALTH_NOTE_SEED, note_id.as_ref()],
+        bump,
+        space = StealthNote::SIZE
+    )]
+    pub stealth_note: Account<'info, StealthNote>,
+
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Inco Lightning program for encrypted operations
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: AccountInfo<'info>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CreateStealthNoteWithOptions<'info>>,
+    note_id: [u8; 32],
+    encrypted_amount: Vec<u8>,
+    lamports: u64,
+    options: NoteOptions,
+) -> Result<()> {
+    if let NoteClaimMode::Split { max_claims } = options.claim_mode {
+        require!(
+            max_claims >= 2 && lamports >= max_claims as u64,
+            VaultError::InvalidClaimMode
+        );
+    }
+
+    // Split and partial claims are authorized by the claim authority's
+    // signature; revealing the secret on the first claim would leak it.
+    require!(
+        options.claim_mode == NoteClaimMode::Single || options.claim_authority.is_some(),
+        VaultError::ClaimAuthorityRequired
+    );
+    require!(
+        options.ephemeral_pubkey.is_none() || options.claim_authority.is_some(),
+        VaultError::InvalidClaimMode
+    );
+
+    let inco_program = ctx.accounts.inco_lightning_program.to_account_info();
+    let signer = ctx.accounts.sender.to_account_info();
+    let clock = Clock::get()?;
+
+    // Transfer real SOL to the stealth note PDA
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        anchor_lang::system_program::Transfer {
+            from: ctx.accounts.sender.to_account_info(),
+            to: ctx.accounts.stealth_note.to_account_info(),
+        },
+    );
+    anchor_lang::system_program::transfer(cpi_ctx, lamports)?;
+
+    // Convert ciphertext -> encrypted handle (input_type = 0)
+    let cpi_ctx = CpiContext::new(inco_program, Operation { signer });
+    let amount_handle: Euint128 = new_euint128(cpi_ctx, encrypted_amount, 0)?;
+
+    let stealth_note = &mut ctx.accounts.stealth_note;
+    stealth_note.note_id = note_id;
+    stealth_note.encrypted_amount = amount_handle;
+    stealth_note.lamports = lamports;
+    stealth_note.original_lamports = lamports;
+    stealth_note.sender = ctx.accounts.sender.key();
+    stealth_note.created_at = clock.unix_timestamp;
+    stealth_note.claimed = false;
+    stealth_note.bump = ctx.bumps.stealth_note;
+    stealth_note.claim_mode = options.claim_mode;
+    stealth_note.claims_made = 0;
+    stealth_note.claim_authority = options.claim_authority.unwrap_or_default();
+    stealth_note.ephemeral_pubkey = options.ephemeral_pubkey.unwrap_or_default();
+    stealth_note.set_id_scheme(options.salt);
+    if let Some(revocable_until) = options.revocable_until {
+        stealth_note.revocable = true;
+        stealth_note.revocable_until = revocable_until;
+    }
+
+    post_memo(
+        options.memo,
+        ctx.accounts.sender.key(),
+        Pubkey::default(),
+        ctx.accounts.stealth_note.key(),
+    )?;
+
+    msg!("Stealth note created with ID: {:?}", note_id);
+
+    Ok(())
+}
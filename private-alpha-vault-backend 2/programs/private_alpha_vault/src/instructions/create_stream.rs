@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use inco_lightning::cpi::accounts::Operation;
+use inco_lightning::cpi::as_euint128;
+use inco_lightning::ID as INCO_LIGHTNING_ID;
+use crate::state::{Stream, UserPosition, Vault};
+use crate::constants::{STREAM_SEED, USER_SEED, VAULT_SEED};
+use crate::errors::VaultError;
+use crate::instructions::memo_utils::post_memo;
+use crate::instructions::position_utils::debit_position;
+use crate::instructions::yield_utils::apply_pending_yield;
+
+/// Open a payment stream funded from the sender's vault position.
+/// `rate * (end_time - start_time)` lamports are escrowed in the stream PDA up front.
+#[derive(Accounts)]
+#[instruction(stream_id: u64)]
+pub struct CreateStream<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [USER_SEED, sender.key().as_ref()],
+        bump = sender_position.bump,
+        constraint = sender_position.owner == sender.key() @ VaultError::Unauthorized
+    )]
+    pub sender_position: Account<'info, UserPosition>,
+
+    #[account(
+        init,
+        payer = sender,
+        seeds = [STREAM_SEED, sender.key().as_ref(), recipient.key().as_ref(), &stream_id.to_le_bytes()],
+        bump,
+        space = Stream::SIZE
+    )]
+    pub stream: Account<'info, Stream>,
+
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    /// CHECK: Recipient address
+    #[account(constraint = recipient.key() != sender.key() @ VaultError::SelfTransfer)]
+    pub recipient: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Inco Lightning program for encrypted operations
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: AccountInfo<'info>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CreateStream<'info>>,
+    stream_id: u64,
+    rate_lamports_per_second: u64,
+    start_time: i64,
+    end_time: i64,
+    memo: Option<Vec<u8>>,
+) -> Result<()> {
+    let inco_program = ctx.accounts.inco_lightning_program.to_account_info();
+    let signer = ctx.accounts.sender.to_account_info();
+    let clock = Clock::get()?;
+
+    // A start in the past would pay out retroactively; clamp it to now.
+    let start_time = start_time.max(clock.unix_timestamp);
+    require!(
+        rate_lamports_per_second > 0 && end_time > start_time,
+        VaultError::InvalidStreamSchedule
+    );
+    let deposited_lamports = rate_lamports_per_second
+        .checked_mul((end_time - start_time) as u64)
+        .ok_or(VaultError::Overflow)?;
+
+    // Apply any pending yield before moving escrow out of the position.
+    apply_pending_yield(
+        &mut ctx.accounts.vault,
+        &mut ctx.accounts.sender_position,
+        inco_program.clone(),
+        signer.clone(),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    // The deposit is public (rate and schedule are), so encrypt it trivially on-chain.
+    let cpi_ctx = CpiContext::new(inco_program.clone(), Operation { signer: signer.clone() });
+    let amount_handle = as_euint128(cpi_ctx, deposited_lamports as u128)?;
+
+    let stream_info = ctx.accounts.stream.to_account_info();
+    debit_position(
+        &mut ctx.accounts.vault,
+        &mut ctx.accounts.sender_position,
+        &stream_info,
+        amount_handle,
+        deposited_lamports,
+        inco_program,
+        signer,
+    )?;
+
+    let stream = &mut ctx.accounts.stream;
+    stream.sender = ctx.accounts.sender.key();
+    stream.recipient = ctx.accounts.recipient.key();
+    stream.stream_id = stream_id;
+    stream.rate_lamports_per_second = rate_lamports_per_second;
+    stream.start_time = start_time;
+    stream.end_time = end_time;
+    stream.deposited_lamports = deposited_lamports;
+    stream.withdrawn_lamports = 0;
+    stream.bump = ctx.bumps.stream;
+
+    post_memo(
+        memo,
+        ctx.accounts.sender.key(),
+        ctx.accounts.recipient.key(),
+        ctx.accounts.stream.key(),
+    )?;
+
+    msg!("Stream {} created: {} lamports", stream_id, deposited_lamports);
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use inco_lightning::cpi::accounts::Operation;
+use inco_lightning::cpi::new_euint128;
+use inco_lightning::types::Euint128;
+use inco_lightning::ID as INCO_LIGHTNING_ID;
+use crate::state::{Subscription, UserPosition};
+use crate::constants::{SUBSCRIPTION_SEED, USER_SEED};
+use crate::errors::VaultError;
+use crate::instructions::memo_utils::post_memo;
+
+/// Authorize a merchant to pull `lamports` (and the matching encrypted amount)
+/// from the payer's position once per period. Nothing moves until charged.
+#[derive(Accounts)]
+#[instruction(subscription_id: u64)]
+pub struct CreateSubscription<'info> {
+    #[account(
+        seeds = [USER_SEED, payer.key().as_ref()],
+        bump = payer_position.bump,
+        constraint = payer_position.owner == payer.key() @ VaultError::Unauthorized
+    )]
+    pub payer_position: Account<'info, UserPosition>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [
+            SUBSCRIPTION_SEED,
+            payer.key().as_ref(),
+            merchant.key().as_ref(),
+            &subscription_id.to_le_bytes(),
+        ],
+        bump,
+        space = Subscription::SIZE
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Merchant address
+    #[account(constraint = merchant.key() != payer.key() @ VaultError::SelfTransfer)]
+    pub merchant: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Inco Lightning program for encrypted operations
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: AccountInfo<'info>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CreateSubscription<'info>>,
+    subscription_id: u64,
+    encrypted_amount: Vec<u8>,
+    lamports: u64,
+    period_seconds: i64,
+    first_due: i64,
+    max_periods: u32,
+    memo: Option<Vec<u8>>,
+) -> Result<()> {
+    require!(period_seconds > 0, VaultError::InvalidSubscriptionPeriod);
+    let clock = Clock::get()?;
+
+    // Convert ciphertext -> encrypted handle (input_type = 0)
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.inco_lightning_program.to_account_info(),
+        Operation { signer: ctx.accounts.payer.to_account_info() },
+    );
+    let amount_handle: Euint128 = new_euint128(cpi_ctx, encrypted_amount, 0)?;
+
+    let subscription = &mut ctx.accounts.subscription;
+    subscription.payer = ctx.accounts.payer.key();
+    subscription.merchant = ctx.accounts.merchant.key();
+    subscription.subscription_id = subscription_id;
+    subscription.encrypted_amount = amount_handle;
+    subscription.lamports = lamports;
+    subscription.period_seconds = period_seconds;
+    subscription.next_due = first_due.max(clock.unix_timestamp);
+    subscription.max_periods = max_periods;
+    subscription.periods_charged = 0;
+    subscription.bump = ctx.bumps.subscription;
+
+    post_memo(
+        memo,
+        ctx.accounts.payer.key(),
+        ctx.accounts.merchant.key(),
+        ctx.accounts.subscription.key(),
+    )?;
+
+    msg!("Subscription {} created", subscription_id);
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+use crate::state::{TimeLock, TokenNote};
+use crate::constants::{NOTE_ID_VERSION, TOKEN_NOTE_SEED};
+use crate::errors::VaultError;
+
+/// Create a stealth note holding SPL / Token-2022 tokens.
+/// Tokens move from the sender's token account into an associated token
+/// account owned by the note PDA. `note_id` is derived from the secret as for
+/// SOL notes, always salted. With `revocable_until` the sender
+/// can take the tokens back with `revoke_token_note` until that lock expires.
+#[derive(Accounts)]
+#[instruction(note_id: [u8; 32])]
+pub struct CreateTokenNote<'info> {
+    #[account(
+        init,
+        payer = sender,
+        seeds = [TOKEN_NOTE_SEED, note_id.as_ref()],
+        bump,
+        space = TokenNote::SIZE
+    )]
+    pub token_note: Account<'info, TokenNote>,
+
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = sender,
+        token::token_program = token_program
+    )]
+    pub sender_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = sender,
+        associated_token::mint = mint,
+        associated_token::authority = token_note,
+        associated_token::token_program = token_program
+    )]
+    pub note_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<CreateTokenNote>,
+    note_id: [u8; 32],
+    amount: u64,
+    salt: [u8; 32],
+    revocable_until: Option<TimeLock>,
+) -> Result<()> {
+    require!(amount > 0, VaultError::InvalidAmount);
+    let clock = Clock::get()?;
+
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.sender_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.note_token_account.to_account_info(),
+            authority: ctx.accounts.sender.to_account_info(),
+        },
+    );
+    transfer_checked(cpi_ctx, amount, ctx.accounts.mint.decimals)?;
+
+    // Token-2022 transfer fees can make the escrowed amount smaller than `amount`.
+    ctx.accounts.note_token_account.reload()?;
+
+    let token_note = &mut ctx.accounts.token_note;
+    token_note.note_id = note_id;
+    token_note.mint = ctx.accounts.mint.key();
+    token_note.token_program = ctx.accounts.token_program.key();
+    token_note.amount = ctx.accounts.note_token_account.amount;
+    token_note.sender = ctx.accounts.sender.key();
+    token_note.created_at = clock.unix_timestamp;
+    token_note.claimed = false;
+    token_note.bump = ctx.bumps.token_note;
+    token_note.id_version = NOTE_ID_VERSION;
+    token_note.salt = salt;
+    if let Some(revocable_until) = revocable_until {
+        token_note.revocable = true;
+        token_note.revocable_until = revocable_until;
+    }
+
+    msg!("Token note created with ID: {:?}", note_id);
+
+    Ok(())
+}
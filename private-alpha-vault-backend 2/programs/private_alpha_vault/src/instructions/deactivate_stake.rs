@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
+use solana_stake_interface::{self as stake, instruction as stake_instruction};
+use crate::state::{Vault, VaultStake};
+use crate::constants::{VAULT_SEED, VAULT_STAKE_SEED};
+use crate::errors::VaultError;
+
+/// Start unstaking a vault stake account (authority only), e.g. to restore
+/// the liquidity buffer. Funds can be withdrawn once the cooldown ends.
+#[derive(Accounts)]
+pub struct DeactivateStake<'info> {
+    #[account(
+        seeds = [VAULT_SEED],
+        bump = vault.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_STAKE_SEED, &vault_stake.stake_id.to_le_bytes()],
+        bump = vault_stake.bump,
+        has_one = stake_account,
+        constraint = !vault_stake.deactivating @ VaultError::StakeAlreadyDeactivated
+    )]
+    pub vault_stake: Account<'info, VaultStake>,
+
+    /// CHECK: Stake account matched against the record
+    #[account(mut)]
+    pub stake_account: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+
+    /// CHECK: Native Stake program
+    #[account(address = stake::program::ID)]
+    pub stake_program: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<DeactivateStake>) -> Result<()> {
+    let vault_key = ctx.accounts.vault.key();
+    let vault_bump = [ctx.accounts.vault.bump];
+    let vault_seeds: &[&[u8]] = &[VAULT_SEED, &vault_bump];
+    invoke_signed(
+        &stake_instruction::deactivate_stake(ctx.accounts.stake_account.key, &vault_key),
+        &[
+            ctx.accounts.stake_account.to_account_info(),
+            ctx.accounts.clock.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+        ],
+        &[vault_seeds],
+    )?;
+
+    ctx.accounts.vault_stake.deactivating = true;
+
+    msg!("Stake {} deactivating", ctx.accounts.vault_stake.stake_id);
+
+    Ok(())
+}
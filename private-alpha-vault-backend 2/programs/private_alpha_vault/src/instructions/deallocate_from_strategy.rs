@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use crate::state::{Strategy, Vault};
+use crate::constants::{STRATEGY_SEED, STRATEGY_SIGNER_SEED, VAULT_SEED};
+use crate::errors::VaultError;
+use crate::instructions::strategy_utils::{strategy_withdraw, StrategyAccounts};
+
+/// Pull `lamports` of principal back from a strategy into the vault pool.
+/// Extra accounts for the strategy's `withdraw` go in remaining accounts.
+#[derive(Accounts)]
+pub struct DeallocateFromStrategy<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [STRATEGY_SEED, strategy_program.key().as_ref()],
+        bump = strategy.bump,
+        has_one = strategy_program,
+        has_one = strategy_state
+    )]
+    pub strategy: Account<'info, Strategy>,
+
+    /// CHECK: Strategy program matched against the registry
+    pub strategy_program: UncheckedAccount<'info>,
+
+    /// CHECK: Strategy state account matched against the registry
+    #[account(mut)]
+    pub strategy_state: UncheckedAccount<'info>,
+
+    /// CHECK: PDA that signs CPIs into this strategy program; holds nothing
+    #[account(seeds = [STRATEGY_SIGNER_SEED, strategy_program.key().as_ref()], bump)]
+    pub strategy_signer: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, DeallocateFromStrategy<'info>>,
+    lamports: u64,
+) -> Result<()> {
+    require!(lamports > 0, VaultError::InvalidAmount);
+
+    let deployed_after = ctx
+        .accounts
+        .strategy
+        .deployed_lamports
+        .checked_sub(lamports)
+        .ok_or(VaultError::InsufficientEscrow)?;
+
+    let strategy_program = ctx.accounts.strategy_program.to_account_info();
+    let strategy_state = ctx.accounts.strategy_state.to_account_info();
+    let strategy_signer = ctx.accounts.strategy_signer.to_account_info();
+    strategy_withdraw(
+        &ctx.accounts.vault,
+        &StrategyAccounts {
+            program: &strategy_program,
+            state: &strategy_state,
+            signer: &strategy_signer,
+            signer_bump: ctx.bumps.strategy_signer,
+            extra: ctx.remaining_accounts,
+        },
+        lamports,
+    )?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.strategy_lamports = vault
+        .strategy_lamports
+        .checked_sub(lamports)
+        .ok_or(VaultError::Underflow)?;
+    ctx.accounts.strategy.deployed_lamports = deployed_after;
+
+    msg!("Deallocated {} lamports from strategy {}", lamports, ctx.accounts.strategy.strategy_program);
+
+    Ok(())
+}
@@ -0,0 +1,168 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    program::{invoke, invoke_signed},
+    sysvar::stake_history,
+};
+use anchor_lang::system_program::{create_account, CreateAccount};
+use solana_sdk_ids::vote;
+use solana_stake_interface::{
+    self as stake,
+    instruction as stake_instruction,
+    state::{Authorized, Lockup, StakeStateV2},
+};
+use crate::state::{Vault, VaultStake};
+use crate::constants::{STAKE_ACCOUNT_SEED, VAULT_SEED, VAULT_STAKE_SEED};
+use crate::errors::VaultError;
+use crate::instructions::position_utils::release_from_pool;
+
+/// Move `lamports` of pooled escrow into a new stake account delegated to
+/// `vote_account`. The vault PDA is the stake account's staker and withdrawer;
+/// the authority only picks the validator and pays the account's rent reserve.
+/// Total delegation is capped at `stake_target_bps` of total escrow.
+#[derive(Accounts)]
+pub struct DelegateStake<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [VAULT_STAKE_SEED, &vault.next_stake_id.to_le_bytes()],
+        bump,
+        space = VaultStake::SIZE
+    )]
+    pub vault_stake: Account<'info, VaultStake>,
+
+    /// CHECK: Stake account PDA, created in the handler
+    #[account(
+        mut,
+        seeds = [STAKE_ACCOUNT_SEED, &vault.next_stake_id.to_le_bytes()],
+        bump
+    )]
+    pub stake_account: UncheckedAccount<'info>,
+
+    /// CHECK: Validator vote account, checked by the Stake program
+    #[account(owner = vote::ID)]
+    pub vote_account: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+    pub rent: Sysvar<'info, Rent>,
+
+    /// CHECK: Stake history sysvar
+    #[account(address = stake_history::ID)]
+    pub stake_history: UncheckedAccount<'info>,
+
+    /// CHECK: Stake config account, still required by DelegateStake
+    #[account(address = stake_config_id())]
+    pub stake_config: UncheckedAccount<'info>,
+
+    /// CHECK: Native Stake program
+    #[account(address = stake::program::ID)]
+    pub stake_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[allow(deprecated)]
+fn stake_config_id() -> Pubkey {
+    stake::config::ID
+}
+
+pub fn handler(ctx: Context<DelegateStake>, lamports: u64) -> Result<()> {
+    let vault = &ctx.accounts.vault;
+    let stake_id = vault.next_stake_id;
+
+    let staked_after = vault
+        .staked_lamports
+        .checked_add(lamports)
+        .ok_or(VaultError::Overflow)?;
+    let stake_cap = (vault.total_escrow_lamports as u128)
+        .saturating_mul(vault.stake_target_bps as u128)
+        / 10_000;
+    require!(lamports > 0, VaultError::InvalidAmount);
+    require!(staked_after as u128 <= stake_cap, VaultError::StakeTargetExceeded);
+
+    // Create the stake account, rent paid by the authority.
+    let stake_space = StakeStateV2::size_of();
+    let rent_reserve = ctx.accounts.rent.minimum_balance(stake_space);
+    let stake_id_bytes = stake_id.to_le_bytes();
+    let stake_bump = [ctx.bumps.stake_account];
+    let stake_seeds: &[&[&[u8]]] = &[&[STAKE_ACCOUNT_SEED, &stake_id_bytes, &stake_bump]];
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.system_program.to_account_info(),
+        CreateAccount {
+            from: ctx.accounts.authority.to_account_info(),
+            to: ctx.accounts.stake_account.to_account_info(),
+        },
+        stake_seeds,
+    );
+    create_account(cpi_ctx, rent_reserve, stake_space as u64, &stake::program::ID)?;
+
+    // Fund it from the pool; this is where the liquidity check happens.
+    release_from_pool(
+        &ctx.accounts.vault,
+        &ctx.accounts.stake_account.to_account_info(),
+        lamports,
+    )?;
+
+    let vault_key = ctx.accounts.vault.key();
+    invoke(
+        &stake_instruction::initialize(
+            ctx.accounts.stake_account.key,
+            &Authorized {
+                staker: vault_key,
+                withdrawer: vault_key,
+            },
+            &Lockup::default(),
+        ),
+        &[
+            ctx.accounts.stake_account.to_account_info(),
+            ctx.accounts.rent.to_account_info(),
+        ],
+    )?;
+
+    let vault_bump = [ctx.accounts.vault.bump];
+    let vault_seeds: &[&[u8]] = &[VAULT_SEED, &vault_bump];
+    invoke_signed(
+        &stake_instruction::delegate_stake(
+            ctx.accounts.stake_account.key,
+            &vault_key,
+            ctx.accounts.vote_account.key,
+        ),
+        &[
+            ctx.accounts.stake_account.to_account_info(),
+            ctx.accounts.vote_account.to_account_info(),
+            ctx.accounts.clock.to_account_info(),
+            ctx.accounts.stake_history.to_account_info(),
+            ctx.accounts.stake_config.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+        ],
+        &[vault_seeds],
+    )?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.staked_lamports = staked_after;
+    vault.next_stake_id = stake_id + 1;
+
+    let vault_stake = &mut ctx.accounts.vault_stake;
+    vault_stake.stake_id = stake_id;
+    vault_stake.stake_account = ctx.accounts.stake_account.key();
+    vault_stake.vote_account = ctx.accounts.vote_account.key();
+    vault_stake.principal_lamports = lamports;
+    vault_stake.rent_reserve = rent_reserve;
+    vault_stake.last_harvest_epoch = ctx.accounts.clock.epoch;
+    vault_stake.deactivating = false;
+    vault_stake.bump = ctx.bumps.vault_stake;
+
+    msg!("Stake {} delegated: {} lamports", stake_id, lamports);
+
+    Ok(())
+}
@@ -3,8 +3,9 @@ use inco_lightning::cpi::accounts::{Allow, Operation};
 use inco_lightning::cpi::{allow, as_euint128, e_add, new_euint128};
 use inco_lightning::types::Euint128;
 use inco_lightning::ID as INCO_LIGHTNING_ID;
-use crate::state::{Vault, UserPosition};
-use crate::errors::VaultError;
+use crate::state::{FeeKind, Vault, UserPosition};
+use crate::instructions::fee_utils::{net_of_fee, take_fee};
+use crate::instructions::position_utils::credit_principal;
 use crate::instructions::yield_utils::apply_pending_yield;
 
 #[derive(Accounts)]
@@ -59,25 +60,23 @@ pub fn handler<'info>(
         ctx.accounts.system_program.to_account_info(),
     )?;
 
-    // Move real SOL into the user's escrow PDA.
+    // Move real SOL into the vault's pooled escrow and credit the user's
+    // principal net of the deposit fee, which stays in the pool.
     let cpi_ctx = CpiContext::new(
         ctx.accounts.system_program.to_account_info(),
         anchor_lang::system_program::Transfer {
             from: ctx.accounts.user.to_account_info(),
-            to: ctx.accounts.user_position.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
         },
     );
     anchor_lang::system_program::transfer(cpi_ctx, lamports)?;
-    ctx.accounts.vault.total_escrow_lamports = ctx
-        .accounts
-        .vault
-        .total_escrow_lamports
-        .checked_add(lamports)
-        .ok_or(VaultError::Overflow)?;
-
-    // Convert ciphertext -> encrypted handle (input_type = 0)
+    let fee = take_fee(&mut ctx.accounts.vault, FeeKind::Deposit, ctx.accounts.user.key(), lamports)?;
+    credit_principal(&mut ctx.accounts.vault, &mut ctx.accounts.user_position, lamports - fee)?;
+
+    // Convert ciphertext -> encrypted handle (input_type = 0), net of the fee
     let cpi_ctx = CpiContext::new(inco_program.clone(), Operation { signer: signer.clone() });
-    let amount_handle: Euint128 = new_euint128(cpi_ctx, encrypted_amount, 0)?;
+    let gross_handle: Euint128 = new_euint128(cpi_ctx, encrypted_amount, 0)?;
+    let amount_handle = net_of_fee(gross_handle, fee, inco_program.clone(), signer.clone())?;
 
     // Add to user's encrypted balance
     let cpi_ctx = CpiContext::new(inco_program.clone(), Operation { signer: signer.clone() });
@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+use inco_lightning::ID as INCO_LIGHTNING_ID;
+use crate::state::{FeeKind, ScheduledTransfer, UserPosition, Vault};
+use crate::constants::{SCHEDULED_TRANSFER_SEED, USER_SEED, VAULT_SEED};
+use crate::errors::VaultError;
+use crate::instructions::fee_utils::credit_position_net_of_fee;
+use crate::instructions::position_utils::init_position_if_new;
+use crate::instructions::yield_utils::apply_pending_yield;
+
+/// Execute a due scheduled transfer. Permissionless so a crank can run it;
+/// the executor pays rent if the recipient position does not exist yet and the
+/// scheduled transfer's own rent goes back to the sender. The recipient is
+/// credited net of the transfer fee.
+#[derive(Accounts)]
+pub struct ExecuteScheduledTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [
+            SCHEDULED_TRANSFER_SEED,
+            scheduled_transfer.sender.as_ref(),
+            &scheduled_transfer.transfer_id.to_le_bytes(),
+        ],
+        bump = scheduled_transfer.bump,
+        has_one = sender @ VaultError::Unauthorized,
+        has_one = recipient @ VaultError::Unauthorized,
+        close = sender
+    )]
+    pub scheduled_transfer: Account<'info, ScheduledTransfer>,
+
+    #[account(
+        init_if_needed,
+        payer = executor,
+        seeds = [USER_SEED, recipient.key().as_ref()],
+        bump,
+        space = UserPosition::SIZE
+    )]
+    pub recipient_position: Account<'info, UserPosition>,
+
+    #[account(mut)]
+    pub executor: Signer<'info>,
+
+    /// CHECK: Original sender, receives the scheduled transfer's rent
+    #[account(mut)]
+    pub sender: UncheckedAccount<'info>,
+
+    /// CHECK: Recipient address, matched against the scheduled transfer
+    pub recipient: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Inco Lightning program for encrypted operations
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: AccountInfo<'info>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteScheduledTransfer<'info>>,
+) -> Result<()> {
+    let inco_program = ctx.accounts.inco_lightning_program.to_account_info();
+    let signer = ctx.accounts.executor.to_account_info();
+    let clock = Clock::get()?;
+
+    require!(
+        ctx.accounts.scheduled_transfer.execute_after.is_unlocked(&clock),
+        VaultError::TransferNotYetExecutable
+    );
+
+    init_position_if_new(
+        &mut ctx.accounts.recipient_position,
+        ctx.accounts.recipient.key(),
+        ctx.bumps.recipient_position,
+        &ctx.accounts.vault,
+        inco_program.clone(),
+        signer.clone(),
+    )?;
+
+    // Apply any pending yield before adding the transferred funds.
+    apply_pending_yield(
+        &mut ctx.accounts.vault,
+        &mut ctx.accounts.recipient_position,
+        inco_program.clone(),
+        signer.clone(),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    let amount_handle = ctx.accounts.scheduled_transfer.encrypted_amount;
+    let lamports = ctx.accounts.scheduled_transfer.lamports;
+    let scheduled_info = ctx.accounts.scheduled_transfer.to_account_info();
+    credit_position_net_of_fee(
+        &mut ctx.accounts.vault,
+        &mut ctx.accounts.recipient_position,
+        &scheduled_info,
+        amount_handle,
+        lamports,
+        Some(FeeKind::Transfer),
+        ctx.accounts.scheduled_transfer.sender,
+        inco_program,
+        signer,
+    )?;
+
+    // NOTE: Recipient calls claimAccess() afterwards to decrypt the new balance.
+
+    msg!("Scheduled transfer {} executed", ctx.accounts.scheduled_transfer.transfer_id);
+
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use inco_lightning::cpi::accounts::Operation;
+use inco_lightning::cpi::{as_euint128, e_sub};
+use inco_lightning::ID as INCO_LIGHTNING_ID;
+use crate::state::{UserPosition, Vault, YieldHistory};
+use crate::constants::{USER_SEED, VAULT_SEED, YIELD_HISTORY_SEED};
+use crate::errors::VaultError;
+use crate::instructions::position_utils::{debit_principal, refresh_boost};
+use crate::instructions::yield_utils::{apply_pending_yield, distribute_penalty};
+
+/// End a lock before it expires. The tier's early-exit penalty is taken from
+/// the position's principal and redistributed at once, without a performance
+/// fee, to every other position as yield; with no other escrow left it stays
+/// in the pool.
+#[derive(Accounts)]
+pub struct ExitLockEarly<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [USER_SEED, user.key().as_ref()],
+        bump = user_position.bump,
+        constraint = user_position.owner == user.key() @ VaultError::Unauthorized
+    )]
+    pub user_position: Account<'info, UserPosition>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Inco Lightning program for encrypted operations
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [YIELD_HISTORY_SEED],
+        bump
+    )]
+    pub yield_history: AccountLoader<'info, YieldHistory>,
+}
+
+pub fn handler(ctx: Context<ExitLockEarly>) -> Result<()> {
+    let inco_program = ctx.accounts.inco_lightning_program.to_account_info();
+    let signer = ctx.accounts.user.to_account_info();
+
+    let now = Clock::get()?.unix_timestamp;
+    let penalty_bps = match ctx.accounts.user_position.lock() {
+        Some(tier) if ctx.accounts.user_position.is_locked(now) => tier.early_exit_penalty_bps,
+        _ => return Err(VaultError::PositionNotLocked.into()),
+    };
+
+    apply_pending_yield(
+        &mut ctx.accounts.vault,
+        &mut ctx.accounts.user_position,
+        inco_program.clone(),
+        signer.clone(),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    let vault = &mut ctx.accounts.vault;
+    let position = &mut ctx.accounts.user_position;
+    position.lock_tier = 0;
+    position.unlock_at = 0;
+    refresh_boost(vault, position)?;
+
+    let penalty = ((position.escrow_lamports as u128) * (penalty_bps as u128) / 10_000) as u64;
+    if penalty > 0 {
+        debit_principal(vault, position, penalty)?;
+
+        let cpi_ctx = CpiContext::new(inco_program.clone(), Operation { signer: signer.clone() });
+        let penalty_handle = as_euint128(cpi_ctx, penalty as u128)?;
+
+        let cpi_ctx = CpiContext::new(inco_program.clone(), Operation { signer: signer.clone() });
+        position.encrypted_balance = e_sub(cpi_ctx, position.encrypted_balance, penalty_handle, 0)?;
+
+        // The lamports never leave the pool; they just stop being principal.
+        // Distributed, they stay in the encrypted total as the other
+        // positions' yield.
+        if !distribute_penalty(vault, &ctx.accounts.yield_history, position, penalty)? {
+            let cpi_ctx = CpiContext::new(inco_program, Operation { signer });
+            vault.total_encrypted_balance = e_sub(cpi_ctx, vault.total_encrypted_balance, penalty_handle, 0)?;
+        }
+    }
+
+    msg!("Position {} left its lock early: {} lamports penalty", ctx.accounts.user.key(), penalty);
+
+    Ok(())
+}
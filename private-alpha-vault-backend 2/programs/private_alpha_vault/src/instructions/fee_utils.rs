@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use inco_lightning::cpi::accounts::Operation;
+use inco_lightning::cpi::{as_euint128, e_sub};
+use inco_lightning::types::Euint128;
+
+use crate::errors::VaultError;
+use crate::events::EntryExitFeeCharged;
+use crate::instructions::position_utils::{credit_position, debit_principal, return_to_pool};
+use crate::state::{FeeKind, UserPosition, Vault};
+
+/// Work out the fee on `lamports` under the vault's schedule and accrue it
+/// for `collect_fees`. The caller keeps the fee lamports in the pool.
+/// Returns the fee.
+pub fn take_fee(vault: &mut Vault, kind: FeeKind, payer: Pubkey, lamports: u64) -> Result<u64> {
+    let fee = vault.entry_exit_fee(kind, lamports);
+    if fee == 0 {
+        return Ok(0);
+    }
+
+    vault.accrued_fees = vault.accrued_fees.checked_add(fee).ok_or(VaultError::Overflow)?;
+    emit!(EntryExitFeeCharged {
+        kind,
+        payer,
+        gross_lamports: (kind != FeeKind::Transfer).then_some(lamports),
+        fee_lamports: fee,
+    });
+    Ok(fee)
+}
+
+/// `amount - fee` as a new encrypted handle, so ciphertext and lamports stay
+/// in step. Returns `amount` unchanged when there is no fee.
+pub fn net_of_fee<'info>(
+    amount: Euint128,
+    fee: u64,
+    inco_program: AccountInfo<'info>,
+    signer: AccountInfo<'info>,
+) -> Result<Euint128> {
+    if fee == 0 {
+        return Ok(amount);
+    }
+
+    let cpi_ctx = CpiContext::new(inco_program.clone(), Operation { signer: signer.clone() });
+    let fee_handle = as_euint128(cpi_ctx, fee as u128)?;
+    let cpi_ctx = CpiContext::new(inco_program, Operation { signer });
+    e_sub(cpi_ctx, amount, fee_handle, 0)
+}
+
+/// Take an already accrued `fee` out of `position`'s principal and encrypted
+/// balance and out of the vault's encrypted total. The lamports stay in the
+/// pool. Returns the position's new encrypted balance handle.
+pub fn charge_position_fee<'info>(
+    vault: &mut Account<'info, Vault>,
+    position: &mut Account<'info, UserPosition>,
+    fee: u64,
+    inco_program: AccountInfo<'info>,
+    signer: AccountInfo<'info>,
+) -> Result<Euint128> {
+    if fee == 0 {
+        return Ok(position.encrypted_balance);
+    }
+
+    debit_principal(vault, position, fee)?;
+
+    let cpi_ctx = CpiContext::new(inco_program.clone(), Operation { signer: signer.clone() });
+    let fee_handle = as_euint128(cpi_ctx, fee as u128)?;
+
+    let cpi_ctx = CpiContext::new(inco_program.clone(), Operation { signer: signer.clone() });
+    let new_balance = e_sub(cpi_ctx, position.encrypted_balance, fee_handle, 0)?;
+    position.encrypted_balance = new_balance;
+
+    let cpi_ctx = CpiContext::new(inco_program, Operation { signer });
+    vault.total_encrypted_balance = e_sub(cpi_ctx, vault.total_encrypted_balance, fee_handle, 0)?;
+
+    Ok(new_balance)
+}
+
+/// `credit_position` net of the `kind` fee: `position` gets `lamports - fee`
+/// and `amount - fee`, and the fee lamports go from `source` into the pool.
+/// For funds that change hands through a holding account (note, stream, ...);
+/// no `kind` charges nothing. Refunds to the party that funded the holding
+/// account use `credit_position` directly.
+/// Returns the position's new encrypted balance handle.
+#[allow(clippy::too_many_arguments)]
+pub fn credit_position_net_of_fee<'info>(
+    vault: &mut Account<'info, Vault>,
+    position: &mut Account<'info, UserPosition>,
+    source: &AccountInfo<'info>,
+    amount: Euint128,
+    lamports: u64,
+    kind: Option<FeeKind>,
+    payer: Pubkey,
+    inco_program: AccountInfo<'info>,
+    signer: AccountInfo<'info>,
+) -> Result<Euint128> {
+    let fee = match kind {
+        Some(kind) => take_fee(vault, kind, payer, lamports)?,
+        None => 0,
+    };
+    let net_amount = net_of_fee(amount, fee, inco_program.clone(), signer.clone())?;
+    return_to_pool(vault, source, fee)?;
+    credit_position(vault, position, source, net_amount, lamports - fee, inco_program, signer)
+}
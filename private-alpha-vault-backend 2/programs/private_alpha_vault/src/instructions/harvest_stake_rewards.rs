@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use inco_lightning::cpi::accounts::Operation;
+use inco_lightning::cpi::as_euint128;
+use inco_lightning::ID as INCO_LIGHTNING_ID;
+use crate::state::{Vault, VaultStake, YieldHistory};
+use crate::constants::{HARVEST_VESTING_PERIOD, VAULT_SEED, VAULT_STAKE_SEED, YIELD_HISTORY_SEED};
+use crate::errors::VaultError;
+use crate::instructions::yield_utils::distribute_yield;
+
+/// Book the rewards a stake account earned since the last harvest and feed
+/// them into `yield_index`. Callable by anyone (crank), once per epoch per
+/// stake account.
+///
+/// Epoch rewards are compounded into the delegation by the Stake program, so
+/// nothing is withdrawn here: the rewards are added to the booked principal
+/// (and so to the pool) and become liquid when the stake is withdrawn.
+#[derive(Accounts)]
+pub struct HarvestStakeRewards<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_STAKE_SEED, &vault_stake.stake_id.to_le_bytes()],
+        bump = vault_stake.bump,
+        has_one = stake_account
+    )]
+    pub vault_stake: Account<'info, VaultStake>,
+
+    /// CHECK: Stake account matched against the record; only its lamports are read
+    pub stake_account: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    /// CHECK: Inco Lightning program for encrypted operations
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [YIELD_HISTORY_SEED],
+        bump
+    )]
+    pub yield_history: AccountLoader<'info, YieldHistory>,
+}
+
+pub fn handler(ctx: Context<HarvestStakeRewards>) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(
+        clock.epoch > ctx.accounts.vault_stake.last_harvest_epoch,
+        VaultError::AlreadyHarvested
+    );
+
+    let rewards = ctx
+        .accounts
+        .vault_stake
+        .unbooked_rewards(ctx.accounts.stake_account.lamports());
+    ctx.accounts.vault_stake.last_harvest_epoch = clock.epoch;
+
+    if rewards > 0 {
+        let inco_program = ctx.accounts.inco_lightning_program.to_account_info();
+        let signer = ctx.accounts.cranker.to_account_info();
+
+        let cpi_ctx = CpiContext::new(inco_program.clone(), Operation { signer: signer.clone() });
+        let rewards_handle = as_euint128(cpi_ctx, rewards as u128)?;
+
+        let vault_stake = &mut ctx.accounts.vault_stake;
+        vault_stake.principal_lamports = vault_stake
+            .principal_lamports
+            .checked_add(rewards)
+            .ok_or(VaultError::Overflow)?;
+        ctx.accounts.vault.staked_lamports = ctx
+            .accounts
+            .vault
+            .staked_lamports
+            .checked_add(rewards)
+            .ok_or(VaultError::Overflow)?;
+
+        distribute_yield(
+            &mut ctx.accounts.vault,
+            &ctx.accounts.yield_history,
+            rewards,
+            rewards_handle,
+            HARVEST_VESTING_PERIOD,
+            inco_program,
+            signer,
+        )?;
+    }
+
+    msg!("Stake {} harvested: {} lamports", ctx.accounts.vault_stake.stake_id, rewards);
+
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+use inco_lightning::cpi::accounts::Operation;
+use inco_lightning::cpi::as_euint128;
+use inco_lightning::ID as INCO_LIGHTNING_ID;
+use crate::state::{Strategy, Vault, YieldHistory};
+use crate::constants::{HARVEST_VESTING_PERIOD, STRATEGY_SEED, STRATEGY_SIGNER_SEED, VAULT_SEED, YIELD_HISTORY_SEED};
+use crate::errors::VaultError;
+use crate::instructions::strategy_utils::{strategy_report_value, strategy_withdraw, StrategyAccounts};
+use crate::instructions::yield_utils::{distribute_yield, socialise_loss};
+
+/// Ask a strategy what the vault's allocation is worth, pull any gain over
+/// deployed principal back into the pool and feed it into `yield_index`.
+/// Callable by anyone (crank). A value below deployed principal is booked
+/// as a realised loss: deployed principal is written down to the value and
+/// the difference is socialised over all positions. A later recovery comes
+/// back as gain.
+///
+/// Remaining accounts are forwarded to both `report_value` and `withdraw`.
+#[derive(Accounts)]
+pub struct HarvestStrategy<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [STRATEGY_SEED, strategy_program.key().as_ref()],
+        bump = strategy.bump,
+        has_one = strategy_program,
+        has_one = strategy_state
+    )]
+    pub strategy: Account<'info, Strategy>,
+
+    /// CHECK: Strategy program matched against the registry
+    pub strategy_program: UncheckedAccount<'info>,
+
+    /// CHECK: Strategy state account matched against the registry
+    #[account(mut)]
+    pub strategy_state: UncheckedAccount<'info>,
+
+    /// CHECK: PDA that signs CPIs into this strategy program; holds nothing
+    #[account(seeds = [STRATEGY_SIGNER_SEED, strategy_program.key().as_ref()], bump)]
+    pub strategy_signer: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    /// CHECK: Inco Lightning program for encrypted operations
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [YIELD_HISTORY_SEED],
+        bump
+    )]
+    pub yield_history: AccountLoader<'info, YieldHistory>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, HarvestStrategy<'info>>) -> Result<()> {
+    let strategy_program = ctx.accounts.strategy_program.to_account_info();
+    let strategy_state = ctx.accounts.strategy_state.to_account_info();
+    let strategy_signer = ctx.accounts.strategy_signer.to_account_info();
+    let strategy_accounts = StrategyAccounts {
+        program: &strategy_program,
+        state: &strategy_state,
+        signer: &strategy_signer,
+        signer_bump: ctx.bumps.strategy_signer,
+        extra: ctx.remaining_accounts,
+    };
+
+    let value = strategy_report_value(&ctx.accounts.vault, &strategy_accounts)?;
+    let deployed = ctx.accounts.strategy.deployed_lamports;
+    if value < deployed {
+        let loss = deployed - value;
+        ctx.accounts.strategy.deployed_lamports = value;
+        ctx.accounts.vault.strategy_lamports = ctx
+            .accounts
+            .vault
+            .strategy_lamports
+            .checked_sub(loss)
+            .ok_or(VaultError::Underflow)?;
+        socialise_loss(&mut ctx.accounts.vault, loss)?;
+    }
+    let gain = value.saturating_sub(ctx.accounts.strategy.deployed_lamports);
+
+    if gain > 0 {
+        strategy_withdraw(&ctx.accounts.vault, &strategy_accounts, gain)?;
+
+        let inco_program = ctx.accounts.inco_lightning_program.to_account_info();
+        let signer = ctx.accounts.cranker.to_account_info();
+
+        let cpi_ctx = CpiContext::new(inco_program.clone(), Operation { signer: signer.clone() });
+        let gain_handle = as_euint128(cpi_ctx, gain as u128)?;
+
+        distribute_yield(
+            &mut ctx.accounts.vault,
+            &ctx.accounts.yield_history,
+            gain,
+            gain_handle,
+            HARVEST_VESTING_PERIOD,
+            inco_program,
+            signer,
+        )?;
+    }
+
+    let strategy = &mut ctx.accounts.strategy;
+    strategy.last_reported_value = value - gain;
+    strategy.last_harvest_at = Clock::get()?.unix_timestamp;
+
+    msg!("Strategy {} harvested: {} lamports", strategy.strategy_program, gain);
+
+    Ok(())
+}
@@ -3,6 +3,7 @@ use inco_lightning::cpi::accounts::Operation;
 use inco_lightning::cpi::as_euint128;
 use inco_lightning::ID as INCO_LIGHTNING_ID;
 use crate::state::Vault;
+use crate::instructions::yield_utils::YIELD_INDEX_SCALE;
 
 #[derive(Accounts)]
 pub struct InitializeVault<'info> {
@@ -30,7 +31,9 @@ pub fn handler(ctx: Context<InitializeVault>) -> Result<()> {
     vault.authority = ctx.accounts.authority.key();
     vault.bump = ctx.bumps.vault;
     vault.total_escrow_lamports = 0;
-    vault.yield_index = 0;
+    // Start above zero: a 0 checkpoint only ever belongs to positions from
+    // before the yield index, which `migrate_position` moves to the index.
+    vault.yield_index = YIELD_INDEX_SCALE;
 
     // Initialize encrypted total balance to encrypted zero.
     let cpi_ctx = CpiContext::new(
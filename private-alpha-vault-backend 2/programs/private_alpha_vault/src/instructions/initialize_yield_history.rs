@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::state::{Vault, YieldHistory};
+use crate::constants::{VAULT_SEED, YIELD_HISTORY_SEED};
+use crate::errors::VaultError;
+
+/// Create the yield history ring buffer. Every instruction that distributes
+/// yield writes to it, so it must exist before the first distribution.
+#[derive(Accounts)]
+pub struct InitializeYieldHistory<'info> {
+    #[account(
+        seeds = [VAULT_SEED],
+        bump = vault.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [YIELD_HISTORY_SEED],
+        bump,
+        space = YieldHistory::SIZE
+    )]
+    pub yield_history: AccountLoader<'info, YieldHistory>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeYieldHistory>) -> Result<()> {
+    let history = &mut ctx.accounts.yield_history.load_init()?;
+    history.head = 0;
+    history.count = 0;
+
+    msg!("Yield history initialized");
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use inco_lightning::ID as INCO_LIGHTNING_ID;
+use crate::state::{UserPosition, Vault, LOCK_TIERS};
+use crate::constants::{USER_SEED, VAULT_SEED};
+use crate::errors::VaultError;
+use crate::instructions::position_utils::refresh_boost;
+use crate::instructions::yield_utils::apply_pending_yield;
+
+/// Lock a position's principal for one of the `LOCK_TIERS` in exchange for a
+/// yield multiplier. While locked, nothing can leave the position except
+/// through `exit_lock_early`; incoming funds join the lock. Relocking is
+/// allowed as long as the new lock does not end sooner. The lock clears itself
+/// on the first settlement after it expires.
+#[derive(Accounts)]
+pub struct LockPosition<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [USER_SEED, user.key().as_ref()],
+        bump = user_position.bump,
+        constraint = user_position.owner == user.key() @ VaultError::Unauthorized
+    )]
+    pub user_position: Account<'info, UserPosition>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Inco Lightning program for encrypted operations
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: AccountInfo<'info>,
+}
+
+pub fn handler(ctx: Context<LockPosition>, lock_tier: u8) -> Result<()> {
+    let tier = (lock_tier as usize)
+        .checked_sub(1)
+        .and_then(|i| LOCK_TIERS.get(i))
+        .ok_or(VaultError::InvalidLockTier)?;
+
+    // Settle at the old weight before the boost changes.
+    apply_pending_yield(
+        &mut ctx.accounts.vault,
+        &mut ctx.accounts.user_position,
+        ctx.accounts.inco_lightning_program.to_account_info(),
+        ctx.accounts.user.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let unlock_at = now.checked_add(tier.duration).ok_or(VaultError::Overflow)?;
+    let position = &mut ctx.accounts.user_position;
+    if position.is_locked(now) {
+        require!(unlock_at >= position.unlock_at, VaultError::LockShortened);
+    }
+
+    position.lock_tier = lock_tier;
+    position.unlock_at = unlock_at;
+    refresh_boost(&mut ctx.accounts.vault, &mut ctx.accounts.user_position)?;
+
+    msg!("Position {} locked at tier {} until {}", ctx.accounts.user.key(), lock_tier, unlock_at);
+
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::MAX_MEMO_LEN;
+use crate::errors::VaultError;
+use crate::events::EncryptedMemo;
+
+/// Publish an optional encrypted memo as an `EncryptedMemo` event.
+/// Memos are opaque to the program; empty or oversized payloads are rejected.
+pub fn post_memo(
+    memo: Option<Vec<u8>>,
+    sender: Pubkey,
+    recipient: Pubkey,
+    reference: Pubkey,
+) -> Result<()> {
+    let Some(memo) = memo else {
+        return Ok(());
+    };
+    require!(
+        !memo.is_empty() && memo.len() <= MAX_MEMO_LEN,
+        VaultError::InvalidMemo
+    );
+    emit!(EncryptedMemo {
+        sender,
+        recipient,
+        reference,
+        memo,
+    });
+    Ok(())
+}
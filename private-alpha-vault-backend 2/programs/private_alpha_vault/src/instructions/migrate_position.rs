@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use crate::state::{UserPosition, Vault};
+use crate::constants::VAULT_SEED;
+use crate::errors::VaultError;
+use crate::instructions::position_utils::return_to_pool;
+
+/// Upgrade a position to the current layout. Positions created before escrow
+/// was pooled in the vault held their principal as PDA lamports; for those
+/// this also moves that principal into the vault pool and records it in
+/// `escrow_lamports`, capped at the vault's `unmigrated_principal`. Later layouts only grow, with new fields zeroed.
+/// Permissionless: `payer` only covers the extra rent.
+#[derive(Accounts)]
+pub struct MigratePosition<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// CHECK: Legacy UserPosition; layout is checked in the handler before it is parsed
+    #[account(mut, owner = crate::ID)]
+    pub user_position: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigratePosition>) -> Result<()> {
+    let position_info = ctx.accounts.user_position.to_account_info();
+    let old_len = position_info.data_len();
+    require!(
+        (UserPosition::LEGACY_SIZE..UserPosition::SIZE).contains(&old_len),
+        VaultError::PositionAlreadyMigrated
+    );
+    let pre_pooling = old_len == UserPosition::LEGACY_SIZE;
+    require!(
+        position_info.try_borrow_data()?[..8] == *UserPosition::DISCRIMINATOR,
+        VaultError::InvalidPositionAccount
+    );
+
+    let rent = Rent::get()?;
+    let legacy_min = rent.minimum_balance(old_len);
+    // Principal was counted in total_escrow_lamports when it was deposited or
+    // claimed, and is still part of `unmigrated_principal`. Lamports above
+    // that are donations and stay in the pool as surplus.
+    let held = if pre_pooling {
+        position_info.lamports().saturating_sub(legacy_min)
+    } else {
+        0
+    };
+    let principal = held.min(ctx.accounts.vault.unmigrated_principal);
+
+    // Pay the rent difference for the larger layout, then grow the account.
+    let top_up = rent
+        .minimum_balance(UserPosition::SIZE)
+        .saturating_sub(legacy_min);
+    if top_up > 0 {
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.payer.to_account_info(),
+                to: position_info.clone(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, top_up)?;
+    }
+    position_info.resize(UserPosition::SIZE)?;
+
+    let mut position = UserPosition::try_deserialize(&mut &position_info.try_borrow_data()?[..])?;
+    if pre_pooling {
+        return_to_pool(&ctx.accounts.vault, &position_info, held)?;
+        ctx.accounts.vault.unmigrated_principal -= principal;
+        position.escrow_lamports = principal;
+        // Positions from before the yield index have no checkpoint; they
+        // start earning from the current index.
+        if position.last_yield_index == 0 {
+            position.last_yield_index = ctx.accounts.vault.yield_index;
+        }
+        position.try_serialize(&mut &mut position_info.try_borrow_mut_data()?[..])?;
+    }
+
+    msg!("Position {} migrated with {} lamports of principal", position.owner, principal);
+
+    Ok(())
+}
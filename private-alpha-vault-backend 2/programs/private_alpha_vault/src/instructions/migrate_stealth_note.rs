@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use crate::state::StealthNote;
+use crate::errors::VaultError;
+
+/// Grow a stealth note created with an older layout to the current one.
+/// New fields are zero-filled, which decodes as a single-claim note with no
+/// extra options, so older notes keep their behaviour. Fees treat them as
+/// funded from the sender's wallet. Permissionless:
+/// `payer` only covers the extra rent.
+#[derive(Accounts)]
+pub struct MigrateStealthNote<'info> {
+    /// CHECK: Older StealthNote; discriminator and size are checked in the handler
+    #[account(mut, owner = crate::ID)]
+    pub stealth_note: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigrateStealthNote>) -> Result<()> {
+    let note_info = ctx.accounts.stealth_note.to_account_info();
+    require!(
+        note_info.data_len() < StealthNote::SIZE,
+        VaultError::NoteAlreadyMigrated
+    );
+    require!(
+        note_info.try_borrow_data()?[..8] == *StealthNote::DISCRIMINATOR,
+        VaultError::InvalidNoteAccount
+    );
+
+    let rent = Rent::get()?;
+    let top_up = rent
+        .minimum_balance(StealthNote::SIZE)
+        .saturating_sub(rent.minimum_balance(note_info.data_len()));
+    if top_up > 0 {
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.payer.to_account_info(),
+                to: note_info.clone(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, top_up)?;
+    }
+    note_info.resize(StealthNote::SIZE)?;
+
+    let mut note = StealthNote::try_deserialize(&mut &note_info.try_borrow_data()?[..])?;
+    if note.original_lamports == 0 {
+        // Notes from before claim modes never had a partial claim.
+        note.original_lamports = note.lamports;
+    }
+    note.try_serialize(&mut &mut note_info.try_borrow_mut_data()?[..])?;
+
+    Ok(())
+}
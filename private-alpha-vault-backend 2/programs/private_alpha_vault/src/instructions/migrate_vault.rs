@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use crate::state::Vault;
+use crate::constants::VAULT_SEED;
+use crate::errors::VaultError;
+
+/// Grow a vault created with an older layout to the current one.
+/// New fields are zero-filled, which leaves every new feature switched off
+/// until the authority configures it. Vaults from before escrow was pooled
+/// also record all of their escrow as `unmigrated_principal`, since it is
+/// still held by the position PDAs, and vaults from before `owed_yield` was
+/// tracked are flagged so settling tolerates the yield missing from it.
+/// Permissionless: `payer` only covers the extra rent.
+#[derive(Accounts)]
+pub struct MigrateVault<'info> {
+    /// CHECK: Older Vault; it cannot be parsed with the current layout until resized
+    #[account(mut, seeds = [VAULT_SEED], bump, owner = crate::ID)]
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigrateVault>) -> Result<()> {
+    let vault_info = ctx.accounts.vault.to_account_info();
+    let old_len = vault_info.data_len();
+    require!(
+        old_len < Vault::SIZE,
+        VaultError::VaultAlreadyMigrated
+    );
+    require!(
+        vault_info.try_borrow_data()?[..8] == *Vault::DISCRIMINATOR,
+        VaultError::Unauthorized
+    );
+
+    // Escrow and yield sit above the old rent minimum, so only the rent
+    // difference for the larger layout is needed.
+    let rent = Rent::get()?;
+    let top_up = rent
+        .minimum_balance(Vault::SIZE)
+        .saturating_sub(rent.minimum_balance(old_len));
+    if top_up > 0 {
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.payer.to_account_info(),
+                to: vault_info.clone(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, top_up)?;
+    }
+    vault_info.resize(Vault::SIZE)?;
+
+    if old_len <= Vault::PRE_OWED_YIELD_SIZE {
+        let mut vault = Vault::try_deserialize(&mut &vault_info.try_borrow_data()?[..])?;
+        if old_len == Vault::LEGACY_SIZE {
+            vault.unmigrated_principal = vault.total_escrow_lamports;
+        }
+        vault.owed_yield_untracked = true;
+        vault.try_serialize(&mut &mut vault_info.try_borrow_mut_data()?[..])?;
+    }
+
+    msg!("Vault migrated to {} bytes", Vault::SIZE);
+
+    Ok(())
+}
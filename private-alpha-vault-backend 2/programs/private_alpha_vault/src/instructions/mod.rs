@@ -8,6 +8,61 @@ pub mod claim_yield;
 pub mod yield_utils;
 pub mod create_stealth_note;
 pub mod claim_stealth_note;
+pub mod position_utils;
+pub mod strategy_utils;
+pub mod fee_utils;
+pub mod batch_transfer;
+pub mod create_stream;
+pub mod withdraw_from_stream;
+pub mod cancel_stream;
+pub mod create_scheduled_transfer;
+pub mod execute_scheduled_transfer;
+pub mod cancel_scheduled_transfer;
+pub mod create_escrow;
+pub mod resolve_escrow;
+pub mod reclaim_escrow;
+pub mod create_subscription;
+pub mod charge_subscription;
+pub mod cancel_subscription;
+pub mod migrate_position;
+pub mod create_stealth_note_from_position;
+pub mod note_utils;
+pub mod create_stealth_note_with_options;
+pub mod claim_note_share;
+pub mod claim_note_partial;
+pub mod migrate_stealth_note;
+pub mod sig_utils;
+pub mod relayed_claim_stealth_note;
+pub mod register_stealth_meta_address;
+pub mod revoke_stealth_note;
+pub mod create_note_batch;
+pub mod claim_batch_note;
+pub mod reclaim_note_batch;
+pub mod memo_utils;
+pub mod create_token_note;
+pub mod claim_token_note;
+pub mod revoke_token_note;
+pub mod migrate_vault;
+pub mod configure_stake;
+pub mod delegate_stake;
+pub mod harvest_stake_rewards;
+pub mod deactivate_stake;
+pub mod withdraw_stake;
+pub mod register_strategy;
+pub mod set_strategy_weight;
+pub mod allocate_to_strategy;
+pub mod deallocate_from_strategy;
+pub mod harvest_strategy;
+pub mod reconcile;
+pub mod sync_position;
+pub mod configure_fees;
+pub mod collect_fees;
+pub mod set_fee_schedule;
+pub mod initialize_yield_history;
+pub mod lock_position;
+pub mod exit_lock_early;
+pub mod settle_positions;
+pub mod set_crank_reward;
 
 pub use initialize_vault::*;
 pub use deposit::*;
@@ -18,3 +73,52 @@ pub use claim_access::*;
 pub use claim_yield::*;
 pub use create_stealth_note::*;
 pub use claim_stealth_note::*;
+pub use batch_transfer::*;
+pub use create_stream::*;
+pub use withdraw_from_stream::*;
+pub use cancel_stream::*;
+pub use create_scheduled_transfer::*;
+pub use execute_scheduled_transfer::*;
+pub use cancel_scheduled_transfer::*;
+pub use create_escrow::*;
+pub use resolve_escrow::*;
+pub use reclaim_escrow::*;
+pub use create_subscription::*;
+pub use charge_subscription::*;
+pub use cancel_subscription::*;
+pub use migrate_position::*;
+pub use create_stealth_note_from_position::*;
+pub use create_stealth_note_with_options::*;
+pub use claim_note_share::*;
+pub use claim_note_partial::*;
+pub use migrate_stealth_note::*;
+pub use relayed_claim_stealth_note::*;
+pub use register_stealth_meta_address::*;
+pub use revoke_stealth_note::*;
+pub use create_note_batch::*;
+pub use claim_batch_note::*;
+pub use reclaim_note_batch::*;
+pub use create_token_note::*;
+pub use claim_token_note::*;
+pub use revoke_token_note::*;
+pub use migrate_vault::*;
+pub use configure_stake::*;
+pub use delegate_stake::*;
+pub use harvest_stake_rewards::*;
+pub use deactivate_stake::*;
+pub use withdraw_stake::*;
+pub use register_strategy::*;
+pub use set_strategy_weight::*;
+pub use allocate_to_strategy::*;
+pub use deallocate_from_strategy::*;
+pub use harvest_strategy::*;
+pub use reconcile::*;
+pub use sync_position::*;
+pub use configure_fees::*;
+pub use collect_fees::*;
+pub use set_fee_schedule::*;
+pub use initialize_yield_history::*;
+pub use lock_position::*;
+pub use exit_lock_early::*;
+pub use settle_positions::*;
+pub use set_crank_reward::*;
@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use inco_lightning::cpi::accounts::Operation;
+use inco_lightning::cpi::{as_euint128, e_sub};
+use inco_lightning::types::Euint128;
+
+use crate::constants::{NOTE_PARTIAL_CLAIM_DOMAIN, NOTE_SHARE_CLAIM_DOMAIN};
+use crate::errors::VaultError;
+use crate::instructions::sig_utils::verify_ed25519_signature;
+use crate::state::StealthNote;
+
+/// Message the note's claim authority signs to let `claimer` claim a share of
+/// a split note. Bound to the claimer, so it is useless to anyone who sees it
+/// on-chain; the claim receipt stops the claimer reusing it.
+pub fn share_claim_message(stealth_note: &Pubkey, claimer: &Pubkey) -> Vec<u8> {
+    let mut message = Vec::with_capacity(NOTE_SHARE_CLAIM_DOMAIN.len() + 32 * 3);
+    message.extend_from_slice(NOTE_SHARE_CLAIM_DOMAIN);
+    message.extend_from_slice(crate::ID.as_ref());
+    message.extend_from_slice(stealth_note.as_ref());
+    message.extend_from_slice(claimer.as_ref());
+    message
+}
+
+/// Message the note's claim authority signs to let `claimer` take `lamports`
+/// from a partial note. Bound to the note's `claims_made`, which every claim
+/// bumps, so each signature approves exactly one claim of exactly that amount.
+pub fn partial_claim_message(
+    stealth_note: &Pubkey,
+    claimer: &Pubkey,
+    lamports: u64,
+    claims_made: u16,
+) -> Vec<u8> {
+    let mut message = Vec::with_capacity(NOTE_PARTIAL_CLAIM_DOMAIN.len() + 32 * 3 + 8 + 2);
+    message.extend_from_slice(NOTE_PARTIAL_CLAIM_DOMAIN);
+    message.extend_from_slice(crate::ID.as_ref());
+    message.extend_from_slice(stealth_note.as_ref());
+    message.extend_from_slice(claimer.as_ref());
+    message.extend_from_slice(&lamports.to_le_bytes());
+    message.extend_from_slice(&claims_made.to_le_bytes());
+    message
+}
+
+/// Require the Ed25519 instruction before this one to be the note's claim
+/// authority signing `share_claim_message` for `claimer`.
+pub fn verify_share_claim(
+    instructions_sysvar: &AccountInfo,
+    note: &Account<StealthNote>,
+    claimer: &Pubkey,
+) -> Result<()> {
+    verify_ed25519_signature(
+        instructions_sysvar,
+        &note.claim_authority,
+        &share_claim_message(&note.key(), claimer),
+    )
+}
+
+/// Require the Ed25519 instruction before this one to be the note's claim
+/// authority signing `partial_claim_message` for `claimer`, `lamports` and the
+/// note's current claim count.
+pub fn verify_partial_claim(
+    instructions_sysvar: &AccountInfo,
+    note: &Account<StealthNote>,
+    claimer: &Pubkey,
+    lamports: u64,
+) -> Result<()> {
+    verify_ed25519_signature(
+        instructions_sysvar,
+        &note.claim_authority,
+        &partial_claim_message(&note.key(), claimer, lamports, note.claims_made),
+    )
+}
+
+/// Carve `lamports` out of a note's remaining funds and return the encrypted
+/// handle for that amount. Taking everything that is left hands over the
+/// note's own encrypted remainder; otherwise the remainder is reduced by an
+/// on-chain encryption of the (public) claim amount.
+/// Updates `lamports`, `claims_made` and `claimed`; moving the SOL is up to the caller.
+pub fn take_from_note<'info>(
+    note: &mut StealthNote,
+    lamports: u64,
+    inco_program: AccountInfo<'info>,
+    signer: AccountInfo<'info>,
+) -> Result<Euint128> {
+    require!(
+        lamports > 0 && lamports <= note.lamports,
+        VaultError::InvalidClaimAmount
+    );
+
+    let amount_handle = if lamports == note.lamports {
+        note.encrypted_amount
+    } else {
+        let cpi_ctx = CpiContext::new(inco_program.clone(), Operation { signer: signer.clone() });
+        let handle = as_euint128(cpi_ctx, lamports as u128)?;
+        let cpi_ctx = CpiContext::new(inco_program, Operation { signer });
+        note.encrypted_amount = e_sub(cpi_ctx, note.encrypted_amount, handle, 0)?;
+        handle
+    };
+
+    note.lamports -= lamports;
+    note.claims_made = note.claims_made.checked_add(1).ok_or(VaultError::Overflow)?;
+    if note.lamports == 0 {
+        note.claimed = true;
+    }
+
+    Ok(amount_handle)
+}
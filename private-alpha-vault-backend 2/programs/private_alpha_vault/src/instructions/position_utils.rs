@@ -0,0 +1,293 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{allocate, assign, create_account, transfer, Allocate, Assign, CreateAccount, Transfer};
+use inco_lightning::cpi::accounts::Operation;
+use inco_lightning::cpi::{as_euint128, e_add, e_sub};
+use inco_lightning::types::Euint128;
+
+use crate::constants::USER_SEED;
+use crate::errors::VaultError;
+use crate::instructions::yield_utils::vest_yield;
+use crate::state::{UserPosition, Vault};
+
+/// Load the `UserPosition` PDA for `owner` from an account passed outside the
+/// `Accounts` struct (e.g. via `remaining_accounts`), creating it if needed.
+///
+/// Mirrors what `init_if_needed` does for the typed instructions: the PDA is
+/// verified against `[USER_SEED, owner]`, rent is paid by `payer`, and a fresh
+/// position gets a valid encrypted zero handle and the current yield index.
+/// Callers must `exit` the returned account to persist any changes.
+pub fn load_or_init_user_position<'info>(
+    position_info: &'info AccountInfo<'info>,
+    owner: &Pubkey,
+    vault: &Vault,
+    payer: AccountInfo<'info>,
+    inco_program: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+) -> Result<Account<'info, UserPosition>> {
+    let (expected, bump) = Pubkey::find_program_address(&[USER_SEED, owner.as_ref()], &crate::ID);
+    require_keys_eq!(position_info.key(), expected, VaultError::InvalidPositionAccount);
+    require!(position_info.is_writable, VaultError::InvalidPositionAccount);
+
+    if position_info.owner == &crate::ID {
+        let mut position = Account::<UserPosition>::try_from(position_info)?;
+        if position.owner == Pubkey::default() {
+            // Created by `init_if_needed` elsewhere but never initialized.
+            let cpi_ctx = CpiContext::new(inco_program, Operation { signer: payer });
+            position.encrypted_balance = as_euint128(cpi_ctx, 0u128)?;
+            position.owner = *owner;
+            position.last_yield_index = vault.yield_index;
+            position.bump = bump;
+        }
+        require_keys_eq!(position.owner, *owner, VaultError::InvalidPositionAccount);
+        return Ok(position);
+    }
+
+    let rent = Rent::get()?;
+    let required = rent.minimum_balance(UserPosition::SIZE);
+    let bump_seed = [bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[USER_SEED, owner.as_ref(), &bump_seed]];
+    let current = position_info.lamports();
+
+    if current == 0 {
+        let cpi_ctx = CpiContext::new_with_signer(
+            system_program,
+            CreateAccount { from: payer.clone(), to: position_info.clone() },
+            signer_seeds,
+        );
+        create_account(cpi_ctx, required, UserPosition::SIZE as u64, &crate::ID)?;
+    } else {
+        // Someone pre-funded the address; top up rent and claim it the same way Anchor does.
+        let top_up = required.saturating_sub(current);
+        if top_up > 0 {
+            let cpi_ctx = CpiContext::new(
+                system_program.clone(),
+                Transfer { from: payer.clone(), to: position_info.clone() },
+            );
+            transfer(cpi_ctx, top_up)?;
+        }
+        let cpi_ctx = CpiContext::new_with_signer(
+            system_program.clone(),
+            Allocate { account_to_allocate: position_info.clone() },
+            signer_seeds,
+        );
+        allocate(cpi_ctx, UserPosition::SIZE as u64)?;
+        let cpi_ctx = CpiContext::new_with_signer(
+            system_program,
+            Assign { account_to_assign: position_info.clone() },
+            signer_seeds,
+        );
+        assign(cpi_ctx, &crate::ID)?;
+    }
+
+    let mut position = Account::<UserPosition>::try_from_unchecked(position_info)?;
+    let cpi_ctx = CpiContext::new(inco_program, Operation { signer: payer });
+    position.encrypted_balance = as_euint128(cpi_ctx, 0u128)?;
+    position.owner = *owner;
+    position.last_yield_index = vault.yield_index;
+    position.bump = bump;
+
+    Ok(position)
+}
+
+/// Give a position created by `init_if_needed` a valid encrypted zero handle
+/// (not just zeroed bytes) and start it at the current yield index.
+pub fn init_position_if_new<'info>(
+    position: &mut Account<'info, UserPosition>,
+    owner: Pubkey,
+    bump: u8,
+    vault: &Vault,
+    inco_program: AccountInfo<'info>,
+    signer: AccountInfo<'info>,
+) -> Result<()> {
+    if position.owner != Pubkey::default() {
+        return Ok(());
+    }
+
+    let cpi_ctx = CpiContext::new(inco_program, Operation { signer });
+    position.encrypted_balance = as_euint128(cpi_ctx, 0u128)?;
+    position.owner = owner;
+    position.bump = bump;
+    position.last_yield_index = vault.yield_index;
+    Ok(())
+}
+
+/// Recompute a position's lock boost from its principal and lock tier and
+/// carry the difference into the vault's total. Callers vest first, since
+/// this changes the weight yield is spread over.
+pub fn refresh_boost(vault: &mut Vault, position: &mut UserPosition) -> Result<()> {
+    let boost = match position.lock() {
+        Some(tier) => {
+            let extra_bps = tier.multiplier_bps.saturating_sub(10_000) as u128;
+            u64::try_from((position.escrow_lamports as u128) * extra_bps / 10_000)
+                .map_err(|_| VaultError::Overflow)?
+        }
+        None => 0,
+    };
+    vault.total_boost_weight = vault
+        .total_boost_weight
+        .checked_sub(position.boost_weight)
+        .and_then(|total| total.checked_add(boost))
+        .ok_or(VaultError::Overflow)?;
+    position.boost_weight = boost;
+    Ok(())
+}
+
+/// Add `lamports` to a position's principal and to the vault's total escrow.
+/// The SOL itself must already be (or be about to be) in the vault pool.
+pub fn credit_principal(vault: &mut Vault, position: &mut UserPosition, lamports: u64) -> Result<()> {
+    // Yield that vested before this change belongs to the old escrow.
+    vest_yield(vault)?;
+    position.escrow_lamports = position
+        .escrow_lamports
+        .checked_add(lamports)
+        .ok_or(VaultError::Overflow)?;
+    vault.total_escrow_lamports = vault
+        .total_escrow_lamports
+        .checked_add(lamports)
+        .ok_or(VaultError::Overflow)?;
+    refresh_boost(vault, position)
+}
+
+/// Remove `lamports` from a position's principal and from the vault's total
+/// escrow. Fails while the position is locked.
+pub fn debit_principal(vault: &mut Vault, position: &mut UserPosition, lamports: u64) -> Result<()> {
+    vest_yield(vault)?;
+    require!(
+        !position.is_locked(Clock::get()?.unix_timestamp),
+        VaultError::PositionLocked
+    );
+    if position.escrow_lamports < lamports || vault.total_escrow_lamports < lamports {
+        return Err(VaultError::InsufficientEscrow.into());
+    }
+    position.escrow_lamports -= lamports;
+    vault.total_escrow_lamports -= lamports;
+    refresh_boost(vault, position)
+}
+
+/// Pay `lamports` out of the vault pool into `destination`.
+/// System Program transfers reject "from" accounts that carry data (like the
+/// vault PDA), so this adjusts lamport balances directly.
+pub fn release_from_pool<'info>(
+    vault: &Account<'info, Vault>,
+    destination: &AccountInfo<'info>,
+    lamports: u64,
+) -> Result<()> {
+    let rent = Rent::get()?;
+    let vault_info = vault.to_account_info();
+    let available = vault_info
+        .lamports()
+        .saturating_sub(rent.minimum_balance(Vault::SIZE));
+    if available < lamports {
+        return Err(VaultError::InsufficientEscrow.into());
+    }
+    **vault_info.try_borrow_mut_lamports()? -= lamports;
+    **destination.try_borrow_mut_lamports()? += lamports;
+    Ok(())
+}
+
+/// Move `lamports` from a program-owned account (note, stream, ...) into the vault pool.
+pub fn return_to_pool<'info>(
+    vault: &Account<'info, Vault>,
+    source: &AccountInfo<'info>,
+    lamports: u64,
+) -> Result<()> {
+    **source.try_borrow_mut_lamports()? -= lamports;
+    **vault.to_account_info().try_borrow_mut_lamports()? += lamports;
+    Ok(())
+}
+
+/// Move `lamports` of `position`'s principal out of the vault pool into a
+/// program-owned holding account (stream, escrow, ...), subtracting `amount`
+/// from the position's encrypted balance and from the vault totals.
+/// Returns the position's new encrypted balance handle.
+pub fn debit_position<'info>(
+    vault: &mut Account<'info, Vault>,
+    position: &mut Account<'info, UserPosition>,
+    destination: &AccountInfo<'info>,
+    amount: Euint128,
+    lamports: u64,
+    inco_program: AccountInfo<'info>,
+    signer: AccountInfo<'info>,
+) -> Result<Euint128> {
+    debit_principal(vault, position, lamports)?;
+
+    let cpi_ctx = CpiContext::new(inco_program.clone(), Operation { signer: signer.clone() });
+    let new_balance = e_sub(cpi_ctx, position.encrypted_balance, amount, 0)?;
+    position.encrypted_balance = new_balance;
+
+    let cpi_ctx = CpiContext::new(inco_program, Operation { signer });
+    vault.total_encrypted_balance = e_sub(cpi_ctx, vault.total_encrypted_balance, amount, 0)?;
+
+    release_from_pool(vault, destination, lamports)?;
+
+    Ok(new_balance)
+}
+
+/// Move `lamports` from a program-owned holding account back into the vault
+/// pool as `position`'s principal, adding `amount` to the position's encrypted
+/// balance and to the vault totals. Callers settle the position's pending
+/// yield first.
+/// Returns the position's new encrypted balance handle.
+pub fn credit_position<'info>(
+    vault: &mut Account<'info, Vault>,
+    position: &mut Account<'info, UserPosition>,
+    source: &AccountInfo<'info>,
+    amount: Euint128,
+    lamports: u64,
+    inco_program: AccountInfo<'info>,
+    signer: AccountInfo<'info>,
+) -> Result<Euint128> {
+    let cpi_ctx = CpiContext::new(inco_program.clone(), Operation { signer: signer.clone() });
+    let new_balance = e_add(cpi_ctx, position.encrypted_balance, amount, 0)?;
+    position.encrypted_balance = new_balance;
+
+    let cpi_ctx = CpiContext::new(inco_program, Operation { signer });
+    vault.total_encrypted_balance = e_add(cpi_ctx, vault.total_encrypted_balance, amount, 0)?;
+
+    return_to_pool(vault, source, lamports)?;
+    credit_principal(vault, position, lamports)?;
+
+    Ok(new_balance)
+}
+
+/// Move `lamports` of principal and `amount` of encrypted balance from one
+/// position to another, as `transfer` does. No SOL moves since escrow is
+/// pooled in the vault; only lock boosts change the vault totals. Fails while
+/// `from` is locked.
+/// Returns the new encrypted balance handles of `(from, to)`.
+pub fn move_between_positions<'info>(
+    vault: &mut Vault,
+    from: &mut Account<'info, UserPosition>,
+    to: &mut Account<'info, UserPosition>,
+    amount: Euint128,
+    lamports: u64,
+    inco_program: AccountInfo<'info>,
+    signer: AccountInfo<'info>,
+) -> Result<(Euint128, Euint128)> {
+    require!(
+        !from.is_locked(Clock::get()?.unix_timestamp),
+        VaultError::PositionLocked
+    );
+    if from.escrow_lamports < lamports {
+        return Err(VaultError::InsufficientEscrow.into());
+    }
+
+    let cpi_ctx = CpiContext::new(inco_program.clone(), Operation { signer: signer.clone() });
+    let new_from_balance = e_sub(cpi_ctx, from.encrypted_balance, amount, 0)?;
+    from.encrypted_balance = new_from_balance;
+
+    let cpi_ctx = CpiContext::new(inco_program, Operation { signer });
+    let new_to_balance = e_add(cpi_ctx, to.encrypted_balance, amount, 0)?;
+    to.encrypted_balance = new_to_balance;
+
+    vest_yield(vault)?;
+    from.escrow_lamports -= lamports;
+    to.escrow_lamports = to
+        .escrow_lamports
+        .checked_add(lamports)
+        .ok_or(VaultError::Overflow)?;
+    refresh_boost(vault, from)?;
+    refresh_boost(vault, to)?;
+
+    Ok((new_from_balance, new_to_balance))
+}
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use inco_lightning::ID as INCO_LIGHTNING_ID;
+use crate::state::{Escrow, UserPosition, Vault};
+use crate::constants::{ESCROW_SEED, USER_SEED, VAULT_SEED};
+use crate::errors::VaultError;
+use crate::instructions::position_utils::credit_position;
+use crate::instructions::yield_utils::apply_pending_yield;
+
+/// Reclaim an unresolved escrow after its timeout. Only the sender can call this.
+#[derive(Accounts)]
+pub struct ReclaimEscrow<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, sender.key().as_ref(), &escrow.escrow_id.to_le_bytes()],
+        bump = escrow.bump,
+        has_one = sender @ VaultError::Unauthorized,
+        close = sender
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(
+        mut,
+        seeds = [USER_SEED, sender.key().as_ref()],
+        bump = sender_position.bump,
+        constraint = sender_position.owner == sender.key() @ VaultError::Unauthorized
+    )]
+    pub sender_position: Account<'info, UserPosition>,
+
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Inco Lightning program for encrypted operations
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: AccountInfo<'info>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ReclaimEscrow<'info>>) -> Result<()> {
+    let inco_program = ctx.accounts.inco_lightning_program.to_account_info();
+    let signer = ctx.accounts.sender.to_account_info();
+    let clock = Clock::get()?;
+
+    require!(
+        ctx.accounts.escrow.reclaimable_after.is_unlocked(&clock),
+        VaultError::EscrowNotExpired
+    );
+
+    apply_pending_yield(
+        &mut ctx.accounts.vault,
+        &mut ctx.accounts.sender_position,
+        inco_program.clone(),
+        signer.clone(),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    let amount_handle = ctx.accounts.escrow.encrypted_amount;
+    let lamports = ctx.accounts.escrow.lamports;
+    let escrow_info = ctx.accounts.escrow.to_account_info();
+    credit_position(
+        &mut ctx.accounts.vault,
+        &mut ctx.accounts.sender_position,
+        &escrow_info,
+        amount_handle,
+        lamports,
+        inco_program,
+        signer,
+    )?;
+
+    msg!("Escrow {} reclaimed after timeout", ctx.accounts.escrow.escrow_id);
+
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use inco_lightning::cpi::accounts::Operation;
+use inco_lightning::cpi::as_euint128;
+use inco_lightning::ID as INCO_LIGHTNING_ID;
+use crate::state::{FeeKind, NoteBatch, UserPosition, Vault};
+use crate::constants::{NOTE_BATCH_SEED, USER_SEED, VAULT_SEED};
+use crate::errors::VaultError;
+use crate::instructions::fee_utils::credit_position_net_of_fee;
+use crate::instructions::position_utils::init_position_if_new;
+use crate::instructions::yield_utils::apply_pending_yield;
+
+/// Take back the notes of an expired batch that nobody claimed. Their
+/// lamports go to the sender's position and the batch account is closed to
+/// the sender.
+#[derive(Accounts)]
+pub struct ReclaimNoteBatch<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [NOTE_BATCH_SEED, sender.key().as_ref(), &note_batch.batch_id.to_le_bytes()],
+        bump = note_batch.bump,
+        has_one = sender @ VaultError::Unauthorized,
+        close = sender
+    )]
+    pub note_batch: Account<'info, NoteBatch>,
+
+    #[account(
+        init_if_needed,
+        payer = sender,
+        seeds = [USER_SEED, sender.key().as_ref()],
+        bump,
+        space = UserPosition::SIZE
+    )]
+    pub sender_position: Account<'info, UserPosition>,
+
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Inco Lightning program for encrypted operations
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: AccountInfo<'info>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ReclaimNoteBatch<'info>>,
+) -> Result<()> {
+    require!(
+        ctx.accounts.note_batch.expires_at.is_unlocked(&Clock::get()?),
+        VaultError::NoteBatchNotExpired
+    );
+
+    let inco_program = ctx.accounts.inco_lightning_program.to_account_info();
+    let signer = ctx.accounts.sender.to_account_info();
+
+    init_position_if_new(
+        &mut ctx.accounts.sender_position,
+        ctx.accounts.sender.key(),
+        ctx.bumps.sender_position,
+        &ctx.accounts.vault,
+        inco_program.clone(),
+        signer.clone(),
+    )?;
+
+    let lamports = ctx.accounts.note_batch.unclaimed_lamports()?;
+    if lamports > 0 {
+        apply_pending_yield(
+            &mut ctx.accounts.vault,
+            &mut ctx.accounts.sender_position,
+            inco_program.clone(),
+            signer.clone(),
+            ctx.accounts.system_program.to_account_info(),
+        )?;
+
+        // The batch only holds one denomination handle; the sender already
+        // knows what went unclaimed, so a trivial encryption reveals nothing new.
+        let cpi_ctx = CpiContext::new(inco_program.clone(), Operation { signer: signer.clone() });
+        let amount_handle = as_euint128(cpi_ctx, lamports as u128)?;
+
+        // The batch was funded from the sender's wallet, so this is a deposit.
+        let batch_info = ctx.accounts.note_batch.to_account_info();
+        credit_position_net_of_fee(
+            &mut ctx.accounts.vault,
+            &mut ctx.accounts.sender_position,
+            &batch_info,
+            amount_handle,
+            lamports,
+            Some(FeeKind::Deposit),
+            ctx.accounts.sender.key(),
+            inco_program,
+            signer,
+        )?;
+    }
+    // The rent leaves with the account when it closes.
+
+    let note_batch = &ctx.accounts.note_batch;
+    msg!(
+        "Note batch {} reclaimed: {} unclaimed notes, {} lamports",
+        note_batch.batch_id,
+        note_batch.leaf_count - note_batch.claimed_count,
+        lamports
+    );
+
+    Ok(())
+}
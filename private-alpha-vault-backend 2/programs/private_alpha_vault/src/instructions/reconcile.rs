@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::state::Vault;
+use crate::constants::VAULT_SEED;
+use crate::errors::VaultError;
+use crate::instructions::yield_utils::{owed_yield_lamports, pool_lamports, pooled_escrow_lamports, YIELD_INDEX_SCALE};
+
+/// Snapshot of the vault's SOL accounting returned by `reconcile`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ReconcileReport {
+    /// Liquid lamports above rent plus staked and strategy principal
+    pub pool_lamports: u64,
+    /// Principal owned by positions
+    pub total_escrow_lamports: u64,
+    /// Part of that principal still held by unmigrated legacy positions
+    pub unmigrated_principal: u64,
+    /// Socialised losses not yet taken from positions, rounded down
+    pub unsettled_loss_lamports: u64,
+    /// Yield in `yield_index` not yet settled, rounded up
+    pub owed_yield_lamports: u64,
+    /// Yield still vesting
+    pub vesting_lamports: u64,
+    /// Performance fees not yet collected
+    pub accrued_fees: u64,
+    /// Pool left over after all of the above (rounding dust, donations)
+    pub surplus_lamports: u64,
+}
+
+/// Read-only check that the pool covers every claim on it: pooled escrow net
+/// of unsettled losses, owed yield, yield still vesting and uncollected fees. Fails with `YieldInvariantViolated`
+/// otherwise. Meant to be simulated; the report is returned as return data.
+#[derive(Accounts)]
+pub struct Reconcile<'info> {
+    #[account(
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+}
+
+pub fn handler(ctx: Context<Reconcile>) -> Result<ReconcileReport> {
+    let vault = &ctx.accounts.vault;
+
+    let pool = pool_lamports(vault)?;
+    let owed = owed_yield_lamports(vault)?;
+    let committed = pooled_escrow_lamports(vault)?
+        .checked_add(owed)
+        .and_then(|v| v.checked_add(vault.vesting_lamports))
+        .and_then(|v| v.checked_add(vault.accrued_fees))
+        .ok_or(VaultError::Overflow)?;
+    require!(committed <= pool, VaultError::YieldInvariantViolated);
+
+    Ok(ReconcileReport {
+        pool_lamports: pool,
+        total_escrow_lamports: vault.total_escrow_lamports,
+        unmigrated_principal: vault.unmigrated_principal,
+        unsettled_loss_lamports: (vault.unsettled_loss / YIELD_INDEX_SCALE) as u64,
+        owed_yield_lamports: owed,
+        vesting_lamports: vault.vesting_lamports,
+        accrued_fees: vault.accrued_fees,
+        surplus_lamports: pool - committed,
+    })
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::state::StealthMetaAddress;
+use crate::constants::STEALTH_META_SEED;
+
+/// Publish or rotate the caller's stealth meta-address.
+/// Rotating only affects notes created afterwards; older notes stay claimable
+/// with the keys they were derived from.
+#[derive(Accounts)]
+pub struct RegisterStealthMetaAddress<'info> {
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [STEALTH_META_SEED, owner.key().as_ref()],
+        bump,
+        space = StealthMetaAddress::SIZE
+    )]
+    pub meta_address: Account<'info, StealthMetaAddress>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<RegisterStealthMetaAddress>,
+    scan_pubkey: [u8; 32],
+    spend_pubkey: [u8; 32],
+) -> Result<()> {
+    let meta_address = &mut ctx.accounts.meta_address;
+    meta_address.owner = ctx.accounts.owner.key();
+    meta_address.scan_pubkey = scan_pubkey;
+    meta_address.spend_pubkey = spend_pubkey;
+    meta_address.bump = ctx.bumps.meta_address;
+
+    msg!("Stealth meta-address registered");
+
+    Ok(())
+}
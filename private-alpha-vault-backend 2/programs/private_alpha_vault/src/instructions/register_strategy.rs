@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use crate::state::{Strategy, Vault};
+use crate::constants::{MAX_DEPLOYED_BPS, STRATEGY_SEED, VAULT_SEED};
+use crate::errors::VaultError;
+
+/// Register a strategy program and the account it holds the vault's
+/// allocation in. The strategy's weight counts against `MAX_DEPLOYED_BPS`
+/// together with the stake target and every other strategy.
+#[derive(Accounts)]
+pub struct RegisterStrategy<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [STRATEGY_SEED, strategy_program.key().as_ref()],
+        bump,
+        space = Strategy::SIZE
+    )]
+    pub strategy: Account<'info, Strategy>,
+
+    /// CHECK: Strategy program, must be executable
+    #[account(executable)]
+    pub strategy_program: UncheckedAccount<'info>,
+
+    /// CHECK: Strategy state account, must be owned by the strategy program
+    #[account(owner = strategy_program.key())]
+    pub strategy_state: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RegisterStrategy>, target_weight_bps: u16) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    let weight_after = vault.strategy_weight_bps as u32 + target_weight_bps as u32;
+    require!(
+        weight_after + vault.stake_target_bps as u32 <= MAX_DEPLOYED_BPS as u32,
+        VaultError::InvalidStakeTarget
+    );
+    vault.strategy_weight_bps = weight_after as u16;
+
+    let strategy = &mut ctx.accounts.strategy;
+    strategy.strategy_program = ctx.accounts.strategy_program.key();
+    strategy.strategy_state = ctx.accounts.strategy_state.key();
+    strategy.target_weight_bps = target_weight_bps;
+    strategy.deployed_lamports = 0;
+    strategy.last_reported_value = 0;
+    strategy.last_harvest_at = Clock::get()?.unix_timestamp;
+    strategy.bump = ctx.bumps.strategy;
+
+    msg!("Strategy {} registered at {} bps", strategy.strategy_program, target_weight_bps);
+
+    Ok(())
+}
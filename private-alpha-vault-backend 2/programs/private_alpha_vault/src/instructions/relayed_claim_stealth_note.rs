@@ -0,0 +1,185 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::ID as INSTRUCTIONS_SYSVAR_ID;
+use inco_lightning::ID as INCO_LIGHTNING_ID;
+use crate::state::{NoteClaimMode, StealthNote, Vault};
+use crate::constants::{RELAYED_CLAIM_DOMAIN, STEALTH_NOTE_SEED, VAULT_SEED};
+use crate::errors::VaultError;
+use crate::instructions::fee_utils::{net_of_fee, take_fee};
+use crate::instructions::position_utils::{credit_position, load_or_init_user_position, return_to_pool};
+use crate::instructions::sig_utils::verify_ed25519_signature;
+use crate::instructions::yield_utils::apply_pending_yield;
+
+/// Where a relayed claim sends the note's funds.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum RelayedClaimDestination {
+    /// Pay plain SOL to the `destination` account
+    Wallet,
+    /// Credit the vault position owned by `destination`
+    Position,
+}
+
+/// Message the note's claim authority signs to authorize a relayed claim.
+/// Binds the note, destination and relayer fee so a relayer cannot change them.
+pub fn relayed_claim_message(
+    stealth_note: &Pubkey,
+    destination: &Pubkey,
+    mode: RelayedClaimDestination,
+    relayer_fee: u64,
+) -> Vec<u8> {
+    let mut message = Vec::with_capacity(RELAYED_CLAIM_DOMAIN.len() + 32 * 3 + 1 + 8);
+    message.extend_from_slice(RELAYED_CLAIM_DOMAIN);
+    message.extend_from_slice(crate::ID.as_ref());
+    message.extend_from_slice(stealth_note.as_ref());
+    message.extend_from_slice(destination.as_ref());
+    message.push(mode as u8);
+    message.extend_from_slice(&relayer_fee.to_le_bytes());
+    message
+}
+
+/// Claim a single-claim stealth note on behalf of its owner.
+/// The relayer (`fee_payer`) submits and pays for the transaction, so the
+/// destination wallet needs no SOL. Instead of revealing the secret, the
+/// claim is authorized by an Ed25519 instruction placed right before this one,
+/// signed by the note's `claim_authority` over `relayed_claim_message`.
+/// A wallet destination must end up rent-exempt, so fresh wallets need to
+/// receive at least the system account minimum. The vault's fee schedule
+/// applies as for the other note payouts (see `StealthNote::payout_fee_kind`):
+/// the relayer fee is paid out like a claim to a wallet and the rest by
+/// `mode`, and both fees come out of what reaches the destination.
+#[derive(Accounts)]
+pub struct RelayedClaimStealthNote<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [STEALTH_NOTE_SEED, stealth_note.note_id.as_ref()],
+        bump = stealth_note.bump,
+        constraint = !stealth_note.claimed @ VaultError::NoteAlreadyClaimed,
+        constraint = stealth_note.claim_mode == NoteClaimMode::Single @ VaultError::WrongClaimMode,
+        constraint = stealth_note.claim_authority != Pubkey::default() @ VaultError::RelayedClaimDisabled
+    )]
+    pub stealth_note: Account<'info, StealthNote>,
+
+    /// CHECK: Recipient wallet (or position owner); bound by the signed message
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    /// CHECK: Instructions sysvar, used to read the preceding Ed25519 instruction
+    #[account(address = INSTRUCTIONS_SYSVAR_ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Inco Lightning program for encrypted operations
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: AccountInfo<'info>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RelayedClaimStealthNote<'info>>,
+    mode: RelayedClaimDestination,
+    relayer_fee: u64,
+) -> Result<()> {
+    let inco_program = ctx.accounts.inco_lightning_program.to_account_info();
+    let signer = ctx.accounts.fee_payer.to_account_info();
+    let note_key = ctx.accounts.stealth_note.key();
+    let destination_key = ctx.accounts.destination.key();
+
+    let message = relayed_claim_message(&note_key, &destination_key, mode, relayer_fee);
+    verify_ed25519_signature(
+        &ctx.accounts.instructions_sysvar,
+        &ctx.accounts.stealth_note.claim_authority,
+        &message,
+    )?;
+
+    let lamports = ctx.accounts.stealth_note.lamports;
+    require!(relayer_fee <= lamports, VaultError::RelayerFeeTooHigh);
+    let payout = lamports - relayer_fee;
+    let sender = ctx.accounts.stealth_note.sender;
+    let mut fee = 0;
+    if let Some(kind) = ctx.accounts.stealth_note.payout_fee_kind(false) {
+        fee += take_fee(&mut ctx.accounts.vault, kind, sender, relayer_fee)?;
+    }
+    let to_position = mode == RelayedClaimDestination::Position;
+    if let Some(kind) = ctx.accounts.stealth_note.payout_fee_kind(to_position) {
+        fee += take_fee(&mut ctx.accounts.vault, kind, sender, payout)?;
+    }
+    require!(fee <= payout, VaultError::RelayerFeeTooHigh);
+    let net_lamports = payout - fee;
+    let note_info = ctx.accounts.stealth_note.to_account_info();
+    return_to_pool(&ctx.accounts.vault, &note_info, fee)?;
+
+    match mode {
+        RelayedClaimDestination::Wallet => {
+            // Use manual lamport adjustment since stealth_note carries data
+            **note_info.try_borrow_mut_lamports()? -= net_lamports;
+            **ctx.accounts.destination.try_borrow_mut_lamports()? += net_lamports;
+        }
+        RelayedClaimDestination::Position => {
+            // remaining_accounts:
+            // [0] destination_position (mut) - created if missing, paid by fee_payer
+            let position_info = ctx
+                .remaining_accounts
+                .first()
+                .ok_or(VaultError::InvalidPositionAccount)?;
+            let mut position = load_or_init_user_position(
+                position_info,
+                &destination_key,
+                &ctx.accounts.vault,
+                signer.clone(),
+                inco_program.clone(),
+                ctx.accounts.system_program.to_account_info(),
+            )?;
+
+            // Apply any pending yield before adding claimed funds
+            apply_pending_yield(
+                &mut ctx.accounts.vault,
+                &mut position,
+                inco_program.clone(),
+                signer.clone(),
+                ctx.accounts.system_program.to_account_info(),
+            )?;
+
+            // The fees come out of the encrypted amount too, keeping ciphertext and principal in step.
+            let net_handle = net_of_fee(
+                ctx.accounts.stealth_note.encrypted_amount,
+                relayer_fee + fee,
+                inco_program.clone(),
+                signer.clone(),
+            )?;
+
+            credit_position(
+                &mut ctx.accounts.vault,
+                &mut position,
+                &note_info,
+                net_handle,
+                net_lamports,
+                inco_program,
+                signer,
+            )?;
+            position.exit(&crate::ID)?;
+        }
+    }
+
+    if relayer_fee > 0 {
+        **note_info.try_borrow_mut_lamports()? -= relayer_fee;
+        **ctx.accounts.fee_payer.to_account_info().try_borrow_mut_lamports()? += relayer_fee;
+    }
+
+    let stealth_note = &mut ctx.accounts.stealth_note;
+    stealth_note.lamports = 0;
+    stealth_note.claims_made = stealth_note.claims_made.saturating_add(1);
+    stealth_note.claimed = true;
+
+    msg!("Stealth note claimed via relayer (fee: {} lamports)", relayer_fee);
+
+    Ok(())
+}
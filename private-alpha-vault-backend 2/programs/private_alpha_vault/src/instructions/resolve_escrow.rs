@@ -0,0 +1,132 @@
+use anchor_lang::prelude::*;
+use inco_lightning::ID as INCO_LIGHTNING_ID;
+use crate::state::{Escrow, FeeKind, UserPosition, Vault};
+use crate::constants::{ESCROW_SEED, USER_SEED, VAULT_SEED};
+use crate::errors::VaultError;
+use crate::instructions::fee_utils::credit_position_net_of_fee;
+use crate::instructions::position_utils::init_position_if_new;
+use crate::instructions::yield_utils::apply_pending_yield;
+
+/// Release an escrow to the recipient or refund it to the sender.
+/// Signed by the arbiter alone, or by sender and recipient together
+/// (`approver` + `co_approver`). The escrow account is closed to the sender.
+/// A release pays the transfer fee; a refund is free.
+#[derive(Accounts)]
+pub struct ResolveEscrow<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, sender.key().as_ref(), &escrow.escrow_id.to_le_bytes()],
+        bump = escrow.bump,
+        has_one = sender @ VaultError::Unauthorized,
+        has_one = recipient @ VaultError::Unauthorized,
+        close = sender
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(
+        mut,
+        seeds = [USER_SEED, sender.key().as_ref()],
+        bump = sender_position.bump
+    )]
+    pub sender_position: Account<'info, UserPosition>,
+
+    #[account(
+        init_if_needed,
+        payer = approver,
+        seeds = [USER_SEED, recipient.key().as_ref()],
+        bump,
+        space = UserPosition::SIZE
+    )]
+    pub recipient_position: Account<'info, UserPosition>,
+
+    #[account(mut)]
+    pub approver: Signer<'info>,
+
+    /// Second party when sender and recipient resolve jointly
+    pub co_approver: Option<Signer<'info>>,
+
+    /// CHECK: Escrow sender, receives the escrow account's rent
+    #[account(mut)]
+    pub sender: UncheckedAccount<'info>,
+
+    /// CHECK: Escrow recipient, matched against the escrow
+    pub recipient: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Inco Lightning program for encrypted operations
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: AccountInfo<'info>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ResolveEscrow<'info>>,
+    release: bool,
+) -> Result<()> {
+    let inco_program = ctx.accounts.inco_lightning_program.to_account_info();
+    let signer = ctx.accounts.approver.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+
+    let co_approver = ctx.accounts.co_approver.as_ref().map(|s| s.key());
+    require!(
+        ctx.accounts
+            .escrow
+            .can_resolve(&ctx.accounts.approver.key(), co_approver.as_ref()),
+        VaultError::EscrowUnauthorized
+    );
+
+    init_position_if_new(
+        &mut ctx.accounts.recipient_position,
+        ctx.accounts.recipient.key(),
+        ctx.bumps.recipient_position,
+        &ctx.accounts.vault,
+        inco_program.clone(),
+        signer.clone(),
+    )?;
+
+    let amount_handle = ctx.accounts.escrow.encrypted_amount;
+    let lamports = ctx.accounts.escrow.lamports;
+    let escrow_info = ctx.accounts.escrow.to_account_info();
+    let fee_kind = release.then_some(FeeKind::Transfer);
+    let payee = if release {
+        &mut ctx.accounts.recipient_position
+    } else {
+        &mut ctx.accounts.sender_position
+    };
+
+    // Apply any pending yield before adding the escrowed funds.
+    apply_pending_yield(
+        &mut ctx.accounts.vault,
+        payee,
+        inco_program.clone(),
+        signer.clone(),
+        system_program,
+    )?;
+
+    credit_position_net_of_fee(
+        &mut ctx.accounts.vault,
+        payee,
+        &escrow_info,
+        amount_handle,
+        lamports,
+        fee_kind,
+        ctx.accounts.escrow.sender,
+        inco_program,
+        signer,
+    )?;
+
+    msg!(
+        "Escrow {} {}",
+        ctx.accounts.escrow.escrow_id,
+        if release { "released" } else { "refunded" }
+    );
+
+    Ok(())
+}
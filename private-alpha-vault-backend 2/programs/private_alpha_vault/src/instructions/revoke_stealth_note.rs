@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use inco_lightning::ID as INCO_LIGHTNING_ID;
+use crate::state::{StealthNote, UserPosition, Vault};
+use crate::constants::{STEALTH_NOTE_SEED, USER_SEED, VAULT_SEED};
+use crate::errors::VaultError;
+use crate::instructions::fee_utils::{credit_position_net_of_fee, take_fee};
+use crate::instructions::position_utils::return_to_pool;
+use crate::instructions::yield_utils::apply_pending_yield;
+
+/// Revoke an unclaimed revocable stealth note before its revocation window
+/// closes. Whatever is left in the note goes back to the sender's position if
+/// `sender_position` is passed, otherwise to the sender's wallet. The note
+/// account is closed to the sender. Only a refund to where the note was
+/// funded from is free; wallet funds going into the position pay the deposit
+/// fee and position funds going to the wallet the withdrawal fee.
+#[derive(Accounts)]
+pub struct RevokeStealthNote<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [STEALTH_NOTE_SEED, stealth_note.note_id.as_ref()],
+        bump = stealth_note.bump,
+        has_one = sender @ VaultError::Unauthorized,
+        constraint = !stealth_note.claimed @ VaultError::NoteAlreadyClaimed,
+        close = sender
+    )]
+    pub stealth_note: Account<'info, StealthNote>,
+
+    /// Refund into this position instead of the wallet
+    #[account(
+        mut,
+        seeds = [USER_SEED, sender.key().as_ref()],
+        bump = sender_position.bump,
+        constraint = sender_position.owner == sender.key() @ VaultError::Unauthorized
+    )]
+    pub sender_position: Option<Account<'info, UserPosition>>,
+
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Inco Lightning program for encrypted operations
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: AccountInfo<'info>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RevokeStealthNote<'info>>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(
+        ctx.accounts.stealth_note.can_revoke(&clock),
+        VaultError::NoteNotRevocable
+    );
+
+    let lamports = ctx.accounts.stealth_note.lamports;
+    let to_position = ctx.accounts.sender_position.is_some();
+    let fee_kind = if to_position && ctx.accounts.stealth_note.funded_from_position {
+        None
+    } else {
+        ctx.accounts.stealth_note.payout_fee_kind(to_position)
+    };
+    let note_info = ctx.accounts.stealth_note.to_account_info();
+    if let Some(sender_position) = ctx.accounts.sender_position.as_mut() {
+        let inco_program = ctx.accounts.inco_lightning_program.to_account_info();
+        let signer = ctx.accounts.sender.to_account_info();
+
+        apply_pending_yield(
+            &mut ctx.accounts.vault,
+            sender_position,
+            inco_program.clone(),
+            signer.clone(),
+            ctx.accounts.system_program.to_account_info(),
+        )?;
+
+        credit_position_net_of_fee(
+            &mut ctx.accounts.vault,
+            sender_position,
+            &note_info,
+            ctx.accounts.stealth_note.encrypted_amount,
+            lamports,
+            fee_kind,
+            ctx.accounts.sender.key(),
+            inco_program,
+            signer,
+        )?;
+    } else if let Some(kind) = fee_kind {
+        let fee = take_fee(&mut ctx.accounts.vault, kind, ctx.accounts.sender.key(), lamports)?;
+        return_to_pool(&ctx.accounts.vault, &note_info, fee)?;
+    }
+    // What is left in a note revoked to the wallet leaves with the rent when
+    // the note closes.
+
+    let stealth_note = &mut ctx.accounts.stealth_note;
+    stealth_note.lamports = 0;
+    stealth_note.claimed = true;
+
+    msg!("Stealth note revoked: {} lamports returned", lamports);
+
+    Ok(())
+}
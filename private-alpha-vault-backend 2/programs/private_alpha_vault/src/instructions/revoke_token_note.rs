@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{
+    close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+    TransferChecked,
+};
+use crate::state::TokenNote;
+use crate::constants::TOKEN_NOTE_SEED;
+use crate::errors::VaultError;
+
+/// Revoke an unclaimed revocable token note before its revocation window
+/// closes. The tokens go back to the sender's associated token account
+/// (created if missing); the note's token account and the note itself are
+/// closed to the sender.
+#[derive(Accounts)]
+pub struct RevokeTokenNote<'info> {
+    #[account(
+        mut,
+        seeds = [TOKEN_NOTE_SEED, token_note.note_id.as_ref()],
+        bump = token_note.bump,
+        has_one = mint,
+        has_one = sender @ VaultError::Unauthorized,
+        constraint = token_note.token_program == token_program.key() @ VaultError::InvalidNoteAccount,
+        constraint = !token_note.claimed @ VaultError::NoteAlreadyClaimed,
+        close = sender
+    )]
+    pub token_note: Account<'info, TokenNote>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = token_note,
+        associated_token::token_program = token_program
+    )]
+    pub note_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = sender,
+        associated_token::mint = mint,
+        associated_token::authority = sender,
+        associated_token::token_program = token_program
+    )]
+    pub sender_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RevokeTokenNote>) -> Result<()> {
+    let clock = Clock::get()?;
+    let token_note = &ctx.accounts.token_note;
+    require!(token_note.can_revoke(&clock), VaultError::NoteNotRevocable);
+
+    let note_id = token_note.note_id;
+    let bump_seed = [token_note.bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[TOKEN_NOTE_SEED, note_id.as_ref(), &bump_seed]];
+
+    let amount = ctx.accounts.note_token_account.amount;
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.note_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.sender_token_account.to_account_info(),
+            authority: ctx.accounts.token_note.to_account_info(),
+        },
+        signer_seeds,
+    );
+    transfer_checked(cpi_ctx, amount, ctx.accounts.mint.decimals)?;
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.note_token_account.to_account_info(),
+            destination: ctx.accounts.sender.to_account_info(),
+            authority: ctx.accounts.token_note.to_account_info(),
+        },
+        signer_seeds,
+    );
+    close_account(cpi_ctx)?;
+
+    msg!("Token note revoked: {} tokens returned", amount);
+
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use crate::state::Vault;
+use crate::constants::{MAX_CRANK_REWARD_LAMPORTS, VAULT_SEED};
+use crate::errors::VaultError;
+
+/// Set the reward paid from accrued fees to `settle_positions` cranks for
+/// each position they settle. Zero disables it.
+#[derive(Accounts)]
+pub struct SetCrankReward<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetCrankReward>, crank_reward_lamports: u64) -> Result<()> {
+    require!(
+        crank_reward_lamports <= MAX_CRANK_REWARD_LAMPORTS,
+        VaultError::InvalidCrankReward
+    );
+    ctx.accounts.vault.crank_reward_lamports = crank_reward_lamports;
+
+    msg!("Crank reward capped at {} lamports per position", crank_reward_lamports);
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use crate::state::{FeeTier, Vault};
+use crate::constants::{MAX_ENTRY_EXIT_FEE_BPS, MAX_FEE_TIERS, VAULT_SEED};
+use crate::errors::VaultError;
+
+/// Set the deposit, withdrawal and transfer fees. `tiers` optionally override
+/// the base fees for larger amounts and must be sorted by strictly increasing
+/// `min_lamports`. Fees accrue with performance fees for `collect_fees`.
+#[derive(Accounts)]
+pub struct SetFeeSchedule<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<SetFeeSchedule>,
+    deposit_fee_bps: u16,
+    withdraw_fee_bps: u16,
+    transfer_fee_bps: u16,
+    tiers: Vec<FeeTier>,
+) -> Result<()> {
+    let within_cap = |bps: u16| bps <= MAX_ENTRY_EXIT_FEE_BPS;
+    require!(
+        within_cap(deposit_fee_bps) && within_cap(withdraw_fee_bps) && within_cap(transfer_fee_bps),
+        VaultError::InvalidFeeSchedule
+    );
+    require!(tiers.len() <= MAX_FEE_TIERS, VaultError::InvalidFeeSchedule);
+    for (i, tier) in tiers.iter().enumerate() {
+        require!(
+            within_cap(tier.deposit_fee_bps)
+                && within_cap(tier.withdraw_fee_bps)
+                && within_cap(tier.transfer_fee_bps),
+            VaultError::InvalidFeeSchedule
+        );
+        if i > 0 {
+            require!(
+                tier.min_lamports > tiers[i - 1].min_lamports,
+                VaultError::InvalidFeeSchedule
+            );
+        }
+    }
+
+    let vault = &mut ctx.accounts.vault;
+    vault.deposit_fee_bps = deposit_fee_bps;
+    vault.withdraw_fee_bps = withdraw_fee_bps;
+    vault.transfer_fee_bps = transfer_fee_bps;
+    vault.fee_tier_count = tiers.len() as u8;
+    vault.fee_tiers = [FeeTier::default(); MAX_FEE_TIERS];
+    vault.fee_tiers[..tiers.len()].copy_from_slice(&tiers);
+
+    msg!(
+        "Fee schedule set: deposit {} / withdraw {} / transfer {} bps, {} tiers",
+        deposit_fee_bps,
+        withdraw_fee_bps,
+        transfer_fee_bps,
+        tiers.len()
+    );
+
+    Ok(())
+}
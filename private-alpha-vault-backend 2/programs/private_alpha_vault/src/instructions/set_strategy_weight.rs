@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::state::{Strategy, Vault};
+use crate::constants::{MAX_DEPLOYED_BPS, STRATEGY_SEED, VAULT_SEED};
+use crate::errors::VaultError;
+
+/// Change a strategy's target weight. Lowering it does not pull anything
+/// back; the authority deallocates to bring the strategy under its new cap.
+#[derive(Accounts)]
+pub struct SetStrategyWeight<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [STRATEGY_SEED, strategy.strategy_program.as_ref()],
+        bump = strategy.bump
+    )]
+    pub strategy: Account<'info, Strategy>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetStrategyWeight>, target_weight_bps: u16) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    let strategy = &mut ctx.accounts.strategy;
+
+    let weight_after = (vault.strategy_weight_bps as u32)
+        .saturating_sub(strategy.target_weight_bps as u32)
+        + target_weight_bps as u32;
+    require!(
+        weight_after + vault.stake_target_bps as u32 <= MAX_DEPLOYED_BPS as u32,
+        VaultError::InvalidStakeTarget
+    );
+    vault.strategy_weight_bps = weight_after as u16;
+    strategy.target_weight_bps = target_weight_bps;
+
+    msg!("Strategy {} weight set to {} bps", strategy.strategy_program, target_weight_bps);
+
+    Ok(())
+}
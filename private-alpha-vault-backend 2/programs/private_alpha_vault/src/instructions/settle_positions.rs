@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use inco_lightning::ID as INCO_LIGHTNING_ID;
+use crate::state::{UserPosition, Vault};
+use crate::constants::{CRANK_REWARD_BPS, MAX_SETTLE_POSITIONS, USER_SEED, VAULT_SEED};
+use crate::errors::VaultError;
+use crate::instructions::position_utils::release_from_pool;
+use crate::instructions::yield_utils::apply_pending_yield;
+
+/// Settle pending yield into principal for many positions at once, so dormant
+/// positions compound and `total_escrow_lamports` stays current. Callable by
+/// anyone (crank). Positions are passed as writable `remaining_accounts`.
+///
+/// The cranker earns `CRANK_REWARD_BPS` of the yield settled into each
+/// position, at most `crank_reward_lamports` per position, paid out of accrued
+/// fees and capped by them. Dust positions earn nothing, so cranking them over
+/// and over does not pay. Owners call `claim_access` to decrypt their new
+/// balance handles.
+#[derive(Accounts)]
+pub struct SettlePositions<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Inco Lightning program for encrypted operations
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: AccountInfo<'info>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, SettlePositions<'info>>) -> Result<()> {
+    require!(
+        !ctx.remaining_accounts.is_empty() && ctx.remaining_accounts.len() <= MAX_SETTLE_POSITIONS,
+        VaultError::InvalidSettleBatch
+    );
+
+    let inco_program = ctx.accounts.inco_lightning_program.to_account_info();
+    let signer = ctx.accounts.cranker.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+
+    let mut settled: u64 = 0;
+    let mut reward: u64 = 0;
+    for position_info in ctx.remaining_accounts.iter() {
+        require!(position_info.is_writable, VaultError::InvalidPositionAccount);
+        let mut position = Account::<UserPosition>::try_from(position_info)?;
+        let expected = Pubkey::create_program_address(
+            &[USER_SEED, position.owner.as_ref(), &[position.bump]],
+            &crate::ID,
+        )
+        .map_err(|_| VaultError::InvalidPositionAccount)?;
+        require_keys_eq!(position_info.key(), expected, VaultError::InvalidPositionAccount);
+
+        let escrow_before = position.escrow_lamports;
+        apply_pending_yield(
+            &mut ctx.accounts.vault,
+            &mut position,
+            inco_program.clone(),
+            signer.clone(),
+            system_program.clone(),
+        )?;
+        // Settling a loss can shrink escrow; only yield earns a reward.
+        if let Some(yield_settled) = position.escrow_lamports.checked_sub(escrow_before) {
+            if yield_settled > 0 {
+                settled += 1;
+                let position_reward =
+                    ((yield_settled as u128) * (CRANK_REWARD_BPS as u128) / 10_000) as u64;
+                reward = reward
+                    .checked_add(position_reward.min(ctx.accounts.vault.crank_reward_lamports))
+                    .ok_or(VaultError::Overflow)?;
+            }
+        }
+
+        position.exit(&crate::ID)?;
+    }
+
+    let reward = reward.min(ctx.accounts.vault.accrued_fees);
+    if reward > 0 {
+        release_from_pool(&ctx.accounts.vault, &signer, reward)?;
+        ctx.accounts.vault.accrued_fees -= reward;
+    }
+
+    msg!("Settled {} of {} positions, crank reward {} lamports", settled, ctx.remaining_accounts.len(), reward);
+
+    Ok(())
+}
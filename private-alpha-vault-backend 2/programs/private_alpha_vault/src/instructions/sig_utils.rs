@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+
+use crate::errors::VaultError;
+
+/// Size of the Ed25519 program's instruction header (count + padding).
+const ED25519_HEADER_LEN: usize = 2;
+/// Size of one Ed25519 signature offsets entry.
+const ED25519_OFFSETS_LEN: usize = 14;
+const PUBKEY_LEN: usize = 32;
+const SIGNATURE_LEN: usize = 64;
+
+/// Require that the instruction right before the current one is an Ed25519
+/// program instruction verifying exactly one signature by `signer` over `message`.
+///
+/// The Ed25519 program has already checked the signature itself by the time we
+/// run; this only checks it covered the key and message we expect. All offsets
+/// must point into the Ed25519 instruction's own data so they cannot be
+/// redirected to other instructions.
+pub fn verify_ed25519_signature(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let current = load_current_index_checked(instructions_sysvar)? as usize;
+    require!(current > 0, VaultError::InvalidSignature);
+    let ix = load_instruction_at_checked(current - 1, instructions_sysvar)?;
+    require_keys_eq!(ix.program_id, ed25519_program::ID, VaultError::InvalidSignature);
+
+    let data = &ix.data;
+    require!(
+        data.len() >= ED25519_HEADER_LEN + ED25519_OFFSETS_LEN && data[0] == 1,
+        VaultError::InvalidSignature
+    );
+
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let offsets = ED25519_HEADER_LEN;
+    let signature_offset = read_u16(offsets) as usize;
+    let signature_ix = read_u16(offsets + 2);
+    let pubkey_offset = read_u16(offsets + 4) as usize;
+    let pubkey_ix = read_u16(offsets + 6);
+    let message_offset = read_u16(offsets + 8) as usize;
+    let message_len = read_u16(offsets + 10) as usize;
+    let message_ix = read_u16(offsets + 12);
+
+    require!(
+        signature_ix == u16::MAX && pubkey_ix == u16::MAX && message_ix == u16::MAX,
+        VaultError::InvalidSignature
+    );
+    require!(
+        signature_offset + SIGNATURE_LEN <= data.len()
+            && pubkey_offset + PUBKEY_LEN <= data.len()
+            && message_offset + message_len <= data.len(),
+        VaultError::InvalidSignature
+    );
+    require!(
+        data[pubkey_offset..pubkey_offset + PUBKEY_LEN] == signer.to_bytes(),
+        VaultError::InvalidSignature
+    );
+    require!(
+        &data[message_offset..message_offset + message_len] == message,
+        VaultError::InvalidSignature
+    );
+
+    Ok(())
+}
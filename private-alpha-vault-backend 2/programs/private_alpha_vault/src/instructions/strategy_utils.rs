@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    hash::hash,
+    instruction::{AccountMeta, Instruction},
+    program::{get_return_data, invoke_signed},
+};
+
+use crate::constants::STRATEGY_SIGNER_SEED;
+use crate::errors::VaultError;
+use crate::instructions::position_utils::release_from_pool;
+use crate::state::Vault;
+
+// Strategy CPI interface. A strategy program exposes three Anchor-style
+// instructions, each taking
+// `[strategy_signer (signer), vault (mut), strategy_state (mut)]` followed by
+// any extra accounts the strategy needs. `strategy_signer` is the PDA
+// `[STRATEGY_SIGNER_SEED, strategy_program]` of this program; the vault PDA
+// itself never signs, so a strategy cannot spend or reassign it.
+//
+// - `deposit(lamports: u64)`: the vault has already credited `lamports` to
+//   `strategy_state`; the strategy puts them to work.
+// - `withdraw(lamports: u64)`: the strategy moves `lamports` from its
+//   accounts back into the vault PDA.
+// - `report_value()`: sets return data to the u64 lamport value the vault's
+//   allocation is currently worth.
+
+fn sighash(name: &str) -> [u8; 8] {
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(&hash(format!("global:{name}").as_bytes()).to_bytes()[..8]);
+    discriminator
+}
+
+/// Accounts every strategy CPI needs besides the vault.
+pub struct StrategyAccounts<'a, 'info> {
+    pub program: &'a AccountInfo<'info>,
+    pub state: &'a AccountInfo<'info>,
+    /// `[STRATEGY_SIGNER_SEED, program]` PDA and its bump
+    pub signer: &'a AccountInfo<'info>,
+    pub signer_bump: u8,
+    /// Forwarded after the fixed accounts
+    pub extra: &'a [AccountInfo<'info>],
+}
+
+fn invoke_strategy<'info>(
+    vault: &Account<'info, Vault>,
+    strategy: &StrategyAccounts<'_, 'info>,
+    data: Vec<u8>,
+) -> Result<()> {
+    let vault_info = vault.to_account_info();
+    let mut metas = vec![
+        AccountMeta::new_readonly(strategy.signer.key(), true),
+        AccountMeta::new(vault_info.key(), false),
+        AccountMeta::new(strategy.state.key(), false),
+    ];
+    let mut infos = vec![strategy.signer.clone(), vault_info, strategy.state.clone()];
+    for account in strategy.extra {
+        metas.push(if account.is_writable {
+            AccountMeta::new(account.key(), account.is_signer)
+        } else {
+            AccountMeta::new_readonly(account.key(), account.is_signer)
+        });
+        infos.push(account.clone());
+    }
+    infos.push(strategy.program.clone());
+
+    let program_key = strategy.program.key();
+    let bump = [strategy.signer_bump];
+    let signer_seeds: &[&[u8]] = &[STRATEGY_SIGNER_SEED, program_key.as_ref(), &bump];
+    invoke_signed(
+        &Instruction {
+            program_id: program_key,
+            accounts: metas,
+            data,
+        },
+        &infos,
+        &[signer_seeds],
+    )?;
+    Ok(())
+}
+
+/// Move `lamports` from the vault pool into the strategy and tell it to deploy them.
+pub fn strategy_deposit<'info>(
+    vault: &Account<'info, Vault>,
+    strategy: &StrategyAccounts<'_, 'info>,
+    lamports: u64,
+) -> Result<()> {
+    release_from_pool(vault, strategy.state, lamports)?;
+
+    let mut data = sighash("deposit").to_vec();
+    data.extend_from_slice(&lamports.to_le_bytes());
+    invoke_strategy(vault, strategy, data)
+}
+
+/// Ask the strategy to return `lamports` to the vault PDA and check that it did.
+pub fn strategy_withdraw<'info>(
+    vault: &Account<'info, Vault>,
+    strategy: &StrategyAccounts<'_, 'info>,
+    lamports: u64,
+) -> Result<()> {
+    let before = vault.to_account_info().lamports();
+
+    let mut data = sighash("withdraw").to_vec();
+    data.extend_from_slice(&lamports.to_le_bytes());
+    invoke_strategy(vault, strategy, data)?;
+
+    let received = vault.to_account_info().lamports().saturating_sub(before);
+    require!(received == lamports, VaultError::StrategyTransferMismatch);
+    Ok(())
+}
+
+/// Current value of the vault's allocation, as reported by the strategy.
+pub fn strategy_report_value<'info>(
+    vault: &Account<'info, Vault>,
+    strategy: &StrategyAccounts<'_, 'info>,
+) -> Result<u64> {
+    invoke_strategy(vault, strategy, sighash("report_value").to_vec())?;
+
+    let (program_id, data) = get_return_data().ok_or(VaultError::MissingStrategyValue)?;
+    require_keys_eq!(program_id, strategy.program.key(), VaultError::MissingStrategyValue);
+    let bytes: [u8; 8] = data
+        .get(..8)
+        .and_then(|b| b.try_into().ok())
+        .ok_or(VaultError::MissingStrategyValue)?;
+    Ok(u64::from_le_bytes(bytes))
+}
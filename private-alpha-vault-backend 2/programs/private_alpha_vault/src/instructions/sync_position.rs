@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use inco_lightning::cpi::accounts::Operation;
+use inco_lightning::cpi::as_euint128;
+use inco_lightning::ID as INCO_LIGHTNING_ID;
+use crate::state::{UserPosition, Vault, YieldHistory};
+use crate::constants::{HARVEST_VESTING_PERIOD, USER_SEED, VAULT_SEED, YIELD_HISTORY_SEED};
+use crate::instructions::position_utils::return_to_pool;
+use crate::instructions::yield_utils::distribute_yield;
+
+/// Sweep lamports sent straight to a position PDA (anything above rent) into
+/// the vault pool. Principal is only ever `escrow_lamports`, so these never
+/// belonged to the position; they are treated as a donation and vest to all
+/// escrow like harvested yield. With no escrow in the vault they stay in the
+/// pool as surplus. Callable by anyone.
+#[derive(Accounts)]
+pub struct SyncPosition<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [USER_SEED, user_position.owner.as_ref()],
+        bump = user_position.bump
+    )]
+    pub user_position: Account<'info, UserPosition>,
+
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    /// CHECK: Inco Lightning program for encrypted operations
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [YIELD_HISTORY_SEED],
+        bump
+    )]
+    pub yield_history: AccountLoader<'info, YieldHistory>,
+}
+
+pub fn handler(ctx: Context<SyncPosition>) -> Result<()> {
+    let position_info = ctx.accounts.user_position.to_account_info();
+    let rent_min = Rent::get()?.minimum_balance(position_info.data_len());
+    let stray = position_info.lamports().saturating_sub(rent_min);
+
+    if stray == 0 {
+        msg!("Position {} has no stray lamports", ctx.accounts.user_position.owner);
+        return Ok(());
+    }
+
+    return_to_pool(&ctx.accounts.vault, &position_info, stray)?;
+
+    if ctx.accounts.vault.total_escrow_lamports > 0 {
+        let inco_program = ctx.accounts.inco_lightning_program.to_account_info();
+        let signer = ctx.accounts.cranker.to_account_info();
+
+        let cpi_ctx = CpiContext::new(inco_program.clone(), Operation { signer: signer.clone() });
+        let stray_handle = as_euint128(cpi_ctx, stray as u128)?;
+
+        distribute_yield(
+            &mut ctx.accounts.vault,
+            &ctx.accounts.yield_history,
+            stray,
+            stray_handle,
+            HARVEST_VESTING_PERIOD,
+            inco_program,
+            signer,
+        )?;
+    }
+
+    msg!("Position {} synced: {} stray lamports donated", ctx.accounts.user_position.owner, stray);
+
+    Ok(())
+}
@@ -1,11 +1,12 @@
 use anchor_lang::prelude::*;
 use inco_lightning::cpi::accounts::{Allow, Operation};
-use inco_lightning::cpi::{allow, as_euint128, e_add, e_sub, new_euint128};
+use inco_lightning::cpi::{allow, as_euint128, new_euint128};
 use inco_lightning::types::Euint128;
 use inco_lightning::ID as INCO_LIGHTNING_ID;
-use crate::state::{UserPosition, Vault};
-use anchor_lang::solana_program::{program::invoke_signed, system_instruction};
-use crate::errors::VaultError;
+use crate::state::{FeeKind, UserPosition, Vault};
+use crate::instructions::fee_utils::{charge_position_fee, net_of_fee, take_fee};
+use crate::instructions::memo_utils::post_memo;
+use crate::instructions::position_utils::move_between_positions;
 use crate::instructions::yield_utils::apply_pending_yield;
 
 #[derive(Accounts)]
@@ -51,6 +52,7 @@ pub fn handler<'info>(
     ctx: Context<'_, '_, 'info, 'info, Transfer<'info>>,
     encrypted_amount: Vec<u8>,
     lamports: u64,
+    memo: Option<Vec<u8>>,
 ) -> Result<()> {
     let inco_program = ctx.accounts.inco_lightning_program.to_account_info();
     let signer = ctx.accounts.sender.to_account_info();
@@ -64,7 +66,7 @@ pub fn handler<'info>(
         ctx.accounts.recipient_position.last_yield_index = ctx.accounts.vault.yield_index;
     }
 
-    // Apply any pending yield for the sender before transferring.
+    // Apply any pending yield for both sides before principal moves.
     apply_pending_yield(
         &mut ctx.accounts.vault,
         &mut ctx.accounts.sender_position,
@@ -72,42 +74,49 @@ pub fn handler<'info>(
         signer.clone(),
         ctx.accounts.system_program.to_account_info(),
     )?;
-
-    let rent = Rent::get()?;
-    let min_balance = rent.minimum_balance(UserPosition::SIZE);
-    let sender_lamports = **ctx.accounts.sender_position.to_account_info().lamports.borrow();
-    let sender_escrow = sender_lamports.saturating_sub(min_balance);
-    if sender_escrow < lamports {
-        return Err(VaultError::InsufficientEscrow.into());
-    }
+    apply_pending_yield(
+        &mut ctx.accounts.vault,
+        &mut ctx.accounts.recipient_position,
+        inco_program.clone(),
+        signer.clone(),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
 
     // Convert ciphertext -> encrypted handle (input_type = 0)
     let cpi_ctx = CpiContext::new(inco_program.clone(), Operation { signer: signer.clone() });
     let amount_handle: Euint128 = new_euint128(cpi_ctx, encrypted_amount, 0)?;
 
-    // Subtract from sender's encrypted balance
-    let cpi_ctx = CpiContext::new(inco_program.clone(), Operation { signer: signer.clone() });
-    let new_sender_balance = e_sub(
-        cpi_ctx,
-        ctx.accounts.sender_position.encrypted_balance,
-        amount_handle,
-        0,
-    )?;
-    ctx.accounts.sender_position.encrypted_balance = new_sender_balance;
+    // The sender is debited `lamports` in total: the recipient gets it net of
+    // the transfer fee and the fee stays in the pool.
+    let fee = take_fee(&mut ctx.accounts.vault, FeeKind::Transfer, ctx.accounts.sender.key(), lamports)?;
+    let net_handle = net_of_fee(amount_handle, fee, inco_program.clone(), signer.clone())?;
 
-    // Add to recipient's encrypted balance
-    let cpi_ctx = CpiContext::new(inco_program.clone(), Operation { signer: signer.clone() });
-    let new_recipient_balance = e_add(
-        cpi_ctx,
-        ctx.accounts.recipient_position.encrypted_balance,
-        amount_handle,
-        0,
+    // Move encrypted balance and principal between positions.
+    // Escrow is pooled in the vault, so no lamports change hands here. The
+    // amount is still public: `lamports` is plaintext instruction data.
+    let (_, new_recipient_balance) = move_between_positions(
+        &mut ctx.accounts.vault,
+        &mut ctx.accounts.sender_position,
+        &mut ctx.accounts.recipient_position,
+        net_handle,
+        lamports - fee,
+        inco_program.clone(),
+        signer.clone(),
+    )?;
+    let new_sender_balance = charge_position_fee(
+        &mut ctx.accounts.vault,
+        &mut ctx.accounts.sender_position,
+        fee,
+        inco_program.clone(),
+        signer.clone(),
     )?;
-    ctx.accounts.recipient_position.encrypted_balance = new_recipient_balance;
 
-    // Move real SOL between user escrow PDAs.
-    **ctx.accounts.sender_position.to_account_info().try_borrow_mut_lamports()? -= lamports;
-    **ctx.accounts.recipient_position.to_account_info().try_borrow_mut_lamports()? += lamports;
+    post_memo(
+        memo,
+        ctx.accounts.sender.key(),
+        ctx.accounts.recipient.key(),
+        ctx.accounts.recipient_position.key(),
+    )?;
 
     // Auto-Authorize: grant decrypt access to both sender and recipient for their new balances.
     // This is OPTIONAL - if remaining_accounts is not provided, skip auto-authorize.
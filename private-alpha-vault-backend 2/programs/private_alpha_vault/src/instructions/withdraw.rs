@@ -3,8 +3,9 @@ use inco_lightning::cpi::accounts::Operation;
 use inco_lightning::cpi::{e_sub, new_euint128};
 use inco_lightning::types::Euint128;
 use inco_lightning::ID as INCO_LIGHTNING_ID;
-use crate::state::{Vault, UserPosition};
-use crate::errors::VaultError;
+use crate::state::{FeeKind, Vault, UserPosition};
+use crate::instructions::fee_utils::take_fee;
+use crate::instructions::position_utils::{debit_principal, release_from_pool};
 use crate::instructions::yield_utils::apply_pending_yield;
 
 #[derive(Accounts)]
@@ -51,16 +52,7 @@ pub fn handler<'info>(
         ctx.accounts.system_program.to_account_info(),
     )?;
 
-    let user_lamports = **ctx.accounts.user_position.to_account_info().lamports.borrow();
-    let rent = Rent::get()?;
-    let min_balance = rent.minimum_balance(UserPosition::SIZE);
-    let escrow_lamports = user_lamports.saturating_sub(min_balance);
-    if escrow_lamports < lamports {
-        return Err(VaultError::InsufficientEscrow.into());
-    }
-    if ctx.accounts.vault.total_escrow_lamports < lamports {
-        return Err(VaultError::InsufficientEscrow.into());
-    }
+    debit_principal(&mut ctx.accounts.vault, &mut ctx.accounts.user_position, lamports)?;
 
     // Convert ciphertext -> encrypted handle (input_type = 0)
     let cpi_ctx = CpiContext::new(inco_program.clone(), Operation { signer: signer.clone() });
@@ -76,23 +68,10 @@ pub fn handler<'info>(
     let new_vault_balance = e_sub(cpi_ctx, ctx.accounts.vault.total_encrypted_balance, amount_handle, 0)?;
     ctx.accounts.vault.total_encrypted_balance = new_vault_balance;
 
-    // Move real SOL out of the user's escrow PDA back to the wallet.
-    // NOTE: System Program transfers reject "from" accounts that carry data (like our PDA).
-    // So we transfer via direct lamport balance adjustment.
-    **ctx.accounts
-        .user_position
-        .to_account_info()
-        .try_borrow_mut_lamports()? -= lamports;
-    **ctx.accounts
-        .user
-        .to_account_info()
-        .try_borrow_mut_lamports()? += lamports;
-
-    ctx.accounts.vault.total_escrow_lamports = ctx
-        .accounts
-        .vault
-        .total_escrow_lamports
-        .saturating_sub(lamports);
+    // Release real SOL from the vault's pooled escrow back to the wallet.
+    // The full amount leaves the position; the withdrawal fee stays in the pool.
+    let fee = take_fee(&mut ctx.accounts.vault, FeeKind::Withdraw, ctx.accounts.user.key(), lamports)?;
+    release_from_pool(&ctx.accounts.vault, &ctx.accounts.user.to_account_info(), lamports - fee)?;
 
     // NOTE: Auto-authorize removed because FHE handles are nondeterministic.
     // Users must call claimAccess() after withdrawal to get decrypt permissions.
@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use inco_lightning::cpi::accounts::Operation;
+use inco_lightning::cpi::as_euint128;
+use inco_lightning::ID as INCO_LIGHTNING_ID;
+use crate::state::{FeeKind, Stream, UserPosition, Vault};
+use crate::constants::{STREAM_SEED, USER_SEED, VAULT_SEED};
+use crate::errors::VaultError;
+use crate::instructions::fee_utils::credit_position_net_of_fee;
+use crate::instructions::position_utils::init_position_if_new;
+use crate::instructions::yield_utils::apply_pending_yield;
+
+/// Pull everything accrued so far on a stream into the recipient's vault
+/// position, net of the transfer fee.
+#[derive(Accounts)]
+pub struct WithdrawFromStream<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [
+            STREAM_SEED,
+            stream.sender.as_ref(),
+            stream.recipient.as_ref(),
+            &stream.stream_id.to_le_bytes(),
+        ],
+        bump = stream.bump,
+        has_one = recipient @ VaultError::Unauthorized
+    )]
+    pub stream: Account<'info, Stream>,
+
+    #[account(
+        init_if_needed,
+        payer = recipient,
+        seeds = [USER_SEED, recipient.key().as_ref()],
+        bump,
+        space = UserPosition::SIZE
+    )]
+    pub recipient_position: Account<'info, UserPosition>,
+
+    #[account(mut)]
+    pub recipient: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Inco Lightning program for encrypted operations
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: AccountInfo<'info>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, WithdrawFromStream<'info>>) -> Result<()> {
+    let inco_program = ctx.accounts.inco_lightning_program.to_account_info();
+    let signer = ctx.accounts.recipient.to_account_info();
+    let clock = Clock::get()?;
+
+    let amount = ctx.accounts.stream.withdrawable_at(clock.unix_timestamp);
+    require!(amount > 0, VaultError::NothingToWithdraw);
+
+    init_position_if_new(
+        &mut ctx.accounts.recipient_position,
+        ctx.accounts.recipient.key(),
+        ctx.bumps.recipient_position,
+        &ctx.accounts.vault,
+        inco_program.clone(),
+        signer.clone(),
+    )?;
+
+    // Apply any pending yield before adding streamed funds.
+    apply_pending_yield(
+        &mut ctx.accounts.vault,
+        &mut ctx.accounts.recipient_position,
+        inco_program.clone(),
+        signer.clone(),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    let cpi_ctx = CpiContext::new(inco_program.clone(), Operation { signer: signer.clone() });
+    let amount_handle = as_euint128(cpi_ctx, amount as u128)?;
+
+    let stream_info = ctx.accounts.stream.to_account_info();
+    credit_position_net_of_fee(
+        &mut ctx.accounts.vault,
+        &mut ctx.accounts.recipient_position,
+        &stream_info,
+        amount_handle,
+        amount,
+        Some(FeeKind::Transfer),
+        ctx.accounts.stream.sender,
+        inco_program,
+        signer,
+    )?;
+
+    ctx.accounts.stream.withdrawn_lamports = ctx
+        .accounts
+        .stream
+        .withdrawn_lamports
+        .checked_add(amount)
+        .ok_or(VaultError::Overflow)?;
+
+    // NOTE: Recipient calls claimAccess() afterwards to decrypt the new balance.
+
+    Ok(())
+}
@@ -0,0 +1,142 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    program::invoke_signed,
+    sysvar::stake_history,
+};
+use solana_stake_interface::{self as stake, instruction as stake_instruction};
+use inco_lightning::cpi::accounts::Operation;
+use inco_lightning::cpi::as_euint128;
+use inco_lightning::ID as INCO_LIGHTNING_ID;
+use crate::state::{Vault, VaultStake, YieldHistory};
+use crate::constants::{HARVEST_VESTING_PERIOD, VAULT_SEED, VAULT_STAKE_SEED, YIELD_HISTORY_SEED};
+use crate::errors::VaultError;
+use crate::instructions::yield_utils::{distribute_yield, socialise_loss};
+
+/// Pull a fully deactivated stake account back into the vault pool.
+/// Callable by anyone (crank). Rewards not yet harvested are distributed,
+/// principal that did not come back (slashing) is socialised as a loss, the
+/// rent reserve goes back to the authority that paid it, and the record is
+/// closed.
+#[derive(Accounts)]
+pub struct WithdrawStake<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_STAKE_SEED, &vault_stake.stake_id.to_le_bytes()],
+        bump = vault_stake.bump,
+        has_one = stake_account,
+        constraint = vault_stake.deactivating @ VaultError::StakeNotDeactivated,
+        close = authority
+    )]
+    pub vault_stake: Account<'info, VaultStake>,
+
+    /// CHECK: Stake account matched against the record
+    #[account(mut)]
+    pub stake_account: UncheckedAccount<'info>,
+
+    /// CHECK: Vault authority, receives the stake account's rent reserve
+    #[account(mut)]
+    pub authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+
+    /// CHECK: Stake history sysvar
+    #[account(address = stake_history::ID)]
+    pub stake_history: UncheckedAccount<'info>,
+
+    /// CHECK: Native Stake program
+    #[account(address = stake::program::ID)]
+    pub stake_program: UncheckedAccount<'info>,
+
+    /// CHECK: Inco Lightning program for encrypted operations
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [YIELD_HISTORY_SEED],
+        bump
+    )]
+    pub yield_history: AccountLoader<'info, YieldHistory>,
+}
+
+pub fn handler(ctx: Context<WithdrawStake>) -> Result<()> {
+    let stake_lamports = ctx.accounts.stake_account.lamports();
+    let rewards = ctx.accounts.vault_stake.unbooked_rewards(stake_lamports);
+    let principal = ctx.accounts.vault_stake.principal_lamports;
+    let rent_reserve = ctx.accounts.vault_stake.rent_reserve;
+
+    // The Stake program rejects this until the cooldown has fully elapsed.
+    let vault_key = ctx.accounts.vault.key();
+    let vault_bump = [ctx.accounts.vault.bump];
+    let vault_seeds: &[&[u8]] = &[VAULT_SEED, &vault_bump];
+    invoke_signed(
+        &stake_instruction::withdraw(
+            ctx.accounts.stake_account.key,
+            &vault_key,
+            &vault_key,
+            stake_lamports,
+            None,
+        ),
+        &[
+            ctx.accounts.stake_account.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.clock.to_account_info(),
+            ctx.accounts.stake_history.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+        ],
+        &[vault_seeds],
+    )?;
+
+    // Everything is liquid again; hand the reserve back to whoever paid it.
+    let reserve_returned = rent_reserve.min(stake_lamports);
+    **ctx.accounts.vault.to_account_info().try_borrow_mut_lamports()? -= reserve_returned;
+    **ctx.accounts.authority.try_borrow_mut_lamports()? += reserve_returned;
+
+    // The booked principal leaves `staked_lamports` whatever came back; any
+    // shortfall is a realised loss.
+    let returned = stake_lamports - reserve_returned;
+    ctx.accounts.vault.staked_lamports = ctx
+        .accounts
+        .vault
+        .staked_lamports
+        .checked_sub(principal)
+        .ok_or(VaultError::Underflow)?;
+    if returned < principal {
+        socialise_loss(&mut ctx.accounts.vault, principal - returned)?;
+    }
+
+    if rewards > 0 {
+        let inco_program = ctx.accounts.inco_lightning_program.to_account_info();
+        let signer = ctx.accounts.cranker.to_account_info();
+        let cpi_ctx = CpiContext::new(inco_program.clone(), Operation { signer: signer.clone() });
+        let rewards_handle = as_euint128(cpi_ctx, rewards as u128)?;
+        distribute_yield(
+            &mut ctx.accounts.vault,
+            &ctx.accounts.yield_history,
+            rewards,
+            rewards_handle,
+            HARVEST_VESTING_PERIOD,
+            inco_program,
+            signer,
+        )?;
+    }
+
+    msg!(
+        "Stake {} withdrawn: {} lamports back in the pool",
+        ctx.accounts.vault_stake.stake_id,
+        returned
+    );
+
+    Ok(())
+}
@@ -2,10 +2,12 @@ use anchor_lang::prelude::*;
 
 pub mod constants;
 pub mod errors;
+pub mod events;
 pub mod state;
 pub mod instructions;
 
 use instructions::*;
+use state::{FeeTier, NoteOptions, TimeLock};
 
 // Will be replaced with actual program ID after deployment
 declare_id!("DmfUVqYJ5DG1iWww8YXt75zsB6RdmMws5qQMBWH4ofvC");
@@ -34,16 +36,22 @@ pub mod private_alpha_vault {
         withdraw::handler(ctx, encrypted_amount, lamports)
     }
 
-    pub fn apply_yield(ctx: Context<ApplyYield>, encrypted_yield: Vec<u8>, lamports: u64) -> Result<()> {
-        apply_yield::handler(ctx, encrypted_yield, lamports)
+    pub fn apply_yield(
+        ctx: Context<ApplyYield>,
+        encrypted_yield: Vec<u8>,
+        lamports: u64,
+        vesting_period: i64,
+    ) -> Result<()> {
+        apply_yield::handler(ctx, encrypted_yield, lamports, vesting_period)
     }
 
     pub fn transfer<'info>(
         ctx: Context<'_, '_, 'info, 'info, Transfer<'info>>,
         encrypted_amount: Vec<u8>,
         lamports: u64,
+        memo: Option<Vec<u8>>,
     ) -> Result<()> {
-        transfer::handler(ctx, encrypted_amount, lamports)
+        transfer::handler(ctx, encrypted_amount, lamports, memo)
     }
 
     /// Claim decrypt access to your own balance handle.
@@ -64,16 +72,427 @@ pub mod private_alpha_vault {
         note_id: [u8; 32],
         encrypted_amount: Vec<u8>,
         lamports: u64,
+        salt: [u8; 32],
+        memo: Option<Vec<u8>>,
     ) -> Result<()> {
-        create_stealth_note::handler(ctx, note_id, encrypted_amount, lamports)
+        create_stealth_note::handler(ctx, note_id, encrypted_amount, lamports, salt, memo)
     }
 
     /// Claim a stealth note by providing the secret passphrase.
-    /// The secret is hashed and matched against the note_id.
+    /// The secret is checked against the note_id under the note's id scheme.
     pub fn claim_stealth_note<'info>(
         ctx: Context<'_, '_, 'info, 'info, ClaimStealthNote<'info>>,
         secret: Vec<u8>,
     ) -> Result<()> {
         claim_stealth_note::handler(ctx, secret)
     }
+
+    /// Transfer to many recipients in one instruction.
+    /// remaining_accounts carry `[recipient, recipient_position]` pairs, one per entry.
+    pub fn batch_transfer<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchTransfer<'info>>,
+        entries: Vec<BatchTransferEntry>,
+    ) -> Result<()> {
+        batch_transfer::handler(ctx, entries)
+    }
+
+    /// Open a payment stream funded from the sender's vault position.
+    pub fn create_stream<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateStream<'info>>,
+        stream_id: u64,
+        rate_lamports_per_second: u64,
+        start_time: i64,
+        end_time: i64,
+        memo: Option<Vec<u8>>,
+    ) -> Result<()> {
+        create_stream::handler(
+            ctx,
+            stream_id,
+            rate_lamports_per_second,
+            start_time,
+            end_time,
+            memo,
+        )
+    }
+
+    /// Pull the accrued amount of a stream into the recipient's position.
+    pub fn withdraw_from_stream<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WithdrawFromStream<'info>>,
+    ) -> Result<()> {
+        withdraw_from_stream::handler(ctx)
+    }
+
+    /// Cancel a stream, paying out accrued funds and refunding the rest to the sender.
+    pub fn cancel_stream<'info>(ctx: Context<'_, '_, 'info, 'info, CancelStream<'info>>) -> Result<()> {
+        cancel_stream::handler(ctx)
+    }
+
+    /// Queue a transfer that executes after a unix timestamp or slot.
+    pub fn create_scheduled_transfer<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateScheduledTransfer<'info>>,
+        transfer_id: u64,
+        encrypted_amount: Vec<u8>,
+        lamports: u64,
+        execute_after: TimeLock,
+        memo: Option<Vec<u8>>,
+    ) -> Result<()> {
+        create_scheduled_transfer::handler(
+            ctx,
+            transfer_id,
+            encrypted_amount,
+            lamports,
+            execute_after,
+            memo,
+        )
+    }
+
+    /// Execute a due scheduled transfer. Callable by anyone (crank).
+    pub fn execute_scheduled_transfer<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteScheduledTransfer<'info>>,
+    ) -> Result<()> {
+        execute_scheduled_transfer::handler(ctx)
+    }
+
+    /// Cancel a scheduled transfer before it becomes executable.
+    pub fn cancel_scheduled_transfer<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CancelScheduledTransfer<'info>>,
+    ) -> Result<()> {
+        cancel_scheduled_transfer::handler(ctx)
+    }
+
+    /// Lock funds from the sender's position into an arbiter-mediated escrow.
+    pub fn create_escrow<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateEscrow<'info>>,
+        escrow_id: u64,
+        encrypted_amount: Vec<u8>,
+        lamports: u64,
+        reclaimable_after: TimeLock,
+        memo: Option<Vec<u8>>,
+    ) -> Result<()> {
+        create_escrow::handler(ctx, escrow_id, encrypted_amount, lamports, reclaimable_after, memo)
+    }
+
+    /// Release an escrow to the recipient (arbiter, or sender and recipient jointly).
+    pub fn release_escrow<'info>(ctx: Context<'_, '_, 'info, 'info, ResolveEscrow<'info>>) -> Result<()> {
+        resolve_escrow::handler(ctx, true)
+    }
+
+    /// Refund an escrow to the sender (arbiter, or sender and recipient jointly).
+    pub fn refund_escrow<'info>(ctx: Context<'_, '_, 'info, 'info, ResolveEscrow<'info>>) -> Result<()> {
+        resolve_escrow::handler(ctx, false)
+    }
+
+    /// Reclaim an unresolved escrow after its timeout.
+    pub fn reclaim_escrow<'info>(ctx: Context<'_, '_, 'info, 'info, ReclaimEscrow<'info>>) -> Result<()> {
+        reclaim_escrow::handler(ctx)
+    }
+
+    /// Authorize a merchant to bill the payer's position once per period.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_subscription<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateSubscription<'info>>,
+        subscription_id: u64,
+        encrypted_amount: Vec<u8>,
+        lamports: u64,
+        period_seconds: i64,
+        first_due: i64,
+        max_periods: u32,
+        memo: Option<Vec<u8>>,
+    ) -> Result<()> {
+        create_subscription::handler(
+            ctx,
+            subscription_id,
+            encrypted_amount,
+            lamports,
+            period_seconds,
+            first_due,
+            max_periods,
+            memo,
+        )
+    }
+
+    /// Charge one due period of a subscription. Callable by anyone (crank).
+    pub fn charge_subscription<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ChargeSubscription<'info>>,
+    ) -> Result<()> {
+        charge_subscription::handler(ctx)
+    }
+
+    /// Cancel a subscription (payer only).
+    pub fn cancel_subscription(ctx: Context<CancelSubscription>) -> Result<()> {
+        cancel_subscription::handler(ctx)
+    }
+
+    /// Move a legacy position's escrow into the vault pool and upgrade its layout.
+    pub fn migrate_position(ctx: Context<MigratePosition>) -> Result<()> {
+        migrate_position::handler(ctx)
+    }
+
+    /// Create a stealth note funded from the sender's shielded vault balance
+    /// rather than their wallet.
+    pub fn create_stealth_note_from_position<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateStealthNoteFromPosition<'info>>,
+        note_id: [u8; 32],
+        encrypted_amount: Vec<u8>,
+        lamports: u64,
+        salt: [u8; 32],
+        memo: Option<Vec<u8>>,
+    ) -> Result<()> {
+        create_stealth_note_from_position::handler(
+            ctx,
+            note_id,
+            encrypted_amount,
+            lamports,
+            salt,
+            memo,
+        )
+    }
+
+    /// Create a stealth note that can be split among several claimers or
+    /// claimed in parts.
+    pub fn create_stealth_note_with_options<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateStealthNoteWithOptions<'info>>,
+        note_id: [u8; 32],
+        encrypted_amount: Vec<u8>,
+        lamports: u64,
+        options: NoteOptions,
+    ) -> Result<()> {
+        create_stealth_note_with_options::handler(ctx, note_id, encrypted_amount, lamports, options)
+    }
+
+    /// Claim one equal share of a split stealth note.
+    pub fn claim_note_share<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimNoteShare<'info>>,
+    ) -> Result<()> {
+        claim_note_share::handler(ctx)
+    }
+
+    /// Claim part of a partial-claim stealth note.
+    pub fn claim_note_partial<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimNotePartial<'info>>,
+        lamports: u64,
+    ) -> Result<()> {
+        claim_note_partial::handler(ctx, lamports)
+    }
+
+    /// Upgrade a stealth note created with an older account layout.
+    pub fn migrate_stealth_note(ctx: Context<MigrateStealthNote>) -> Result<()> {
+        migrate_stealth_note::handler(ctx)
+    }
+
+    /// Claim a stealth note through a relayer that pays the transaction fees.
+    /// Authorized by an Ed25519 signature from the note's claim authority.
+    pub fn relayed_claim_stealth_note<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RelayedClaimStealthNote<'info>>,
+        mode: RelayedClaimDestination,
+        relayer_fee: u64,
+    ) -> Result<()> {
+        relayed_claim_stealth_note::handler(ctx, mode, relayer_fee)
+    }
+
+    /// Publish or rotate the caller's stealth meta-address (scan and spend keys).
+    pub fn register_stealth_meta_address(
+        ctx: Context<RegisterStealthMetaAddress>,
+        scan_pubkey: [u8; 32],
+        spend_pubkey: [u8; 32],
+    ) -> Result<()> {
+        register_stealth_meta_address::handler(ctx, scan_pubkey, spend_pubkey)
+    }
+
+    /// Revoke an unclaimed revocable stealth note (sender only), refunding the
+    /// remainder to the sender's wallet or position.
+    pub fn revoke_stealth_note<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RevokeStealthNote<'info>>,
+    ) -> Result<()> {
+        revoke_stealth_note::handler(ctx)
+    }
+
+    /// Issue a batch of same-denomination stealth notes committed by a Merkle root.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_note_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateNoteBatch<'info>>,
+        batch_id: u64,
+        merkle_root: [u8; 32],
+        leaf_count: u32,
+        salt: [u8; 32],
+        encrypted_denomination: Vec<u8>,
+        denomination_lamports: u64,
+        expires_at: TimeLock,
+    ) -> Result<()> {
+        create_note_batch::handler(
+            ctx,
+            batch_id,
+            merkle_root,
+            leaf_count,
+            salt,
+            encrypted_denomination,
+            denomination_lamports,
+            expires_at,
+        )
+    }
+
+    /// Claim one note of a batch with its secret and Merkle proof.
+    pub fn claim_batch_note<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimBatchNote<'info>>,
+        leaf_index: u32,
+        secret: Vec<u8>,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        claim_batch_note::handler(ctx, leaf_index, secret, proof)
+    }
+
+    /// Return an expired batch's unclaimed notes to the sender's position
+    /// and close the batch (sender only).
+    pub fn reclaim_note_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ReclaimNoteBatch<'info>>,
+    ) -> Result<()> {
+        reclaim_note_batch::handler(ctx)
+    }
+
+    /// Create a stealth note holding SPL / Token-2022 tokens.
+    pub fn create_token_note(
+        ctx: Context<CreateTokenNote>,
+        note_id: [u8; 32],
+        amount: u64,
+        salt: [u8; 32],
+        revocable_until: Option<TimeLock>,
+    ) -> Result<()> {
+        create_token_note::handler(ctx, note_id, amount, salt, revocable_until)
+    }
+
+    /// Claim a token note into the claimer's associated token account.
+    pub fn claim_token_note(ctx: Context<ClaimTokenNote>, secret: Vec<u8>) -> Result<()> {
+        claim_token_note::handler(ctx, secret)
+    }
+
+    /// Revoke an unclaimed revocable token note (sender only), returning the
+    /// tokens to the sender's associated token account.
+    pub fn revoke_token_note(ctx: Context<RevokeTokenNote>) -> Result<()> {
+        revoke_token_note::handler(ctx)
+    }
+
+    /// Grow an older vault account to the current layout.
+    pub fn migrate_vault(ctx: Context<MigrateVault>) -> Result<()> {
+        migrate_vault::handler(ctx)
+    }
+
+    /// Set the share of pooled escrow that may be staked (authority only).
+    pub fn configure_stake(ctx: Context<ConfigureStake>, stake_target_bps: u16) -> Result<()> {
+        configure_stake::handler(ctx, stake_target_bps)
+    }
+
+    /// Delegate pooled escrow to a validator through a new stake account (authority only).
+    pub fn delegate_stake(ctx: Context<DelegateStake>, lamports: u64) -> Result<()> {
+        delegate_stake::handler(ctx, lamports)
+    }
+
+    /// Feed a stake account's epoch rewards into the yield index. Callable by anyone (crank).
+    pub fn harvest_stake_rewards(ctx: Context<HarvestStakeRewards>) -> Result<()> {
+        harvest_stake_rewards::handler(ctx)
+    }
+
+    /// Start unstaking a vault stake account (authority only).
+    pub fn deactivate_stake(ctx: Context<DeactivateStake>) -> Result<()> {
+        deactivate_stake::handler(ctx)
+    }
+
+    /// Return a deactivated stake account to the pool. Callable by anyone (crank).
+    pub fn withdraw_stake(ctx: Context<WithdrawStake>) -> Result<()> {
+        withdraw_stake::handler(ctx)
+    }
+
+    /// Register a yield strategy program with a target weight (authority only).
+    pub fn register_strategy(ctx: Context<RegisterStrategy>, target_weight_bps: u16) -> Result<()> {
+        register_strategy::handler(ctx, target_weight_bps)
+    }
+
+    /// Change a strategy's target weight (authority only).
+    pub fn set_strategy_weight(ctx: Context<SetStrategyWeight>, target_weight_bps: u16) -> Result<()> {
+        set_strategy_weight::handler(ctx, target_weight_bps)
+    }
+
+    /// Move pooled escrow into a strategy, up to its target weight (authority only).
+    pub fn allocate_to_strategy<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AllocateToStrategy<'info>>,
+        lamports: u64,
+    ) -> Result<()> {
+        allocate_to_strategy::handler(ctx, lamports)
+    }
+
+    /// Pull principal back from a strategy into the pool (authority only).
+    pub fn deallocate_from_strategy<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DeallocateFromStrategy<'info>>,
+        lamports: u64,
+    ) -> Result<()> {
+        deallocate_from_strategy::handler(ctx, lamports)
+    }
+
+    /// Feed a strategy's gains into the yield index. Callable by anyone (crank).
+    pub fn harvest_strategy<'info>(
+        ctx: Context<'_, '_, 'info, 'info, HarvestStrategy<'info>>,
+    ) -> Result<()> {
+        harvest_strategy::handler(ctx)
+    }
+
+    /// Check that the pool covers escrow plus owed and vesting yield (read-only).
+    pub fn reconcile(ctx: Context<Reconcile>) -> Result<ReconcileReport> {
+        reconcile::handler(ctx)
+    }
+
+    /// Donate lamports sent directly to a position PDA to the pool. Callable by anyone (crank).
+    pub fn sync_position(ctx: Context<SyncPosition>) -> Result<()> {
+        sync_position::handler(ctx)
+    }
+
+    /// Set the performance fee and its recipient (authority only).
+    pub fn configure_fees(
+        ctx: Context<ConfigureFees>,
+        performance_fee_bps: u16,
+        fee_recipient: Pubkey,
+    ) -> Result<()> {
+        configure_fees::handler(ctx, performance_fee_bps, fee_recipient)
+    }
+
+    /// Pay accrued performance fees to the fee recipient.
+    pub fn collect_fees(ctx: Context<CollectFees>) -> Result<()> {
+        collect_fees::handler(ctx)
+    }
+
+    /// Set deposit, withdrawal and transfer fees with optional amount tiers (authority only).
+    pub fn set_fee_schedule(
+        ctx: Context<SetFeeSchedule>,
+        deposit_fee_bps: u16,
+        withdraw_fee_bps: u16,
+        transfer_fee_bps: u16,
+        tiers: Vec<FeeTier>,
+    ) -> Result<()> {
+        set_fee_schedule::handler(ctx, deposit_fee_bps, withdraw_fee_bps, transfer_fee_bps, tiers)
+    }
+
+    /// Create the yield distribution history account (authority only).
+    pub fn initialize_yield_history(ctx: Context<InitializeYieldHistory>) -> Result<()> {
+        initialize_yield_history::handler(ctx)
+    }
+
+    /// Lock a position's principal for a boosted yield multiplier.
+    pub fn lock_position(ctx: Context<LockPosition>, lock_tier: u8) -> Result<()> {
+        lock_position::handler(ctx, lock_tier)
+    }
+
+    /// Leave a lock before it expires, paying the tier's early-exit penalty to other holders.
+    pub fn exit_lock_early(ctx: Context<ExitLockEarly>) -> Result<()> {
+        exit_lock_early::handler(ctx)
+    }
+
+    /// Settle pending yield for many positions passed as remaining accounts. Callable by anyone (crank).
+    pub fn settle_positions<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettlePositions<'info>>,
+    ) -> Result<()> {
+        settle_positions::handler(ctx)
+    }
+
+    /// Set the per-position reward for `settle_positions` cranks (authority only).
+    pub fn set_crank_reward(ctx: Context<SetCrankReward>, crank_reward_lamports: u64) -> Result<()> {
+        set_crank_reward::handler(ctx, crank_reward_lamports)
+    }
 }
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use inco_lightning::types::Euint128;
+
+use super::TimeLock;
+
+/// Conditional payment locked from the sender's position. The arbiter, or the
+/// sender and recipient together, decide whether it is released to the
+/// recipient or refunded; after `reclaimable_after` the sender may reclaim it.
+#[account]
+pub struct Escrow {
+    /// Position owner that locked the funds
+    pub sender: Pubkey,
+    /// Owner of the position paid on release
+    pub recipient: Pubkey,
+    /// Third party allowed to release or refund on its own
+    pub arbiter: Pubkey,
+    /// Caller-chosen id so a sender can open several escrows
+    pub escrow_id: u64,
+    /// Encrypted amount debited from the sender at creation
+    pub encrypted_amount: Euint128,
+    /// Real SOL lamports escrowed in this PDA
+    pub lamports: u64,
+    /// Timestamp when the escrow was opened
+    pub created_at: i64,
+    /// Timeout after which the sender can reclaim unilaterally
+    pub reclaimable_after: TimeLock,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl Escrow {
+    pub const SIZE: usize = 8 + // discriminator
+        32 +  // sender
+        32 +  // recipient
+        32 +  // arbiter
+        8 +   // escrow_id
+        16 +  // Euint128 handle (128-bit)
+        8 +   // lamports
+        8 +   // created_at
+        TimeLock::SIZE + // reclaimable_after
+        1;    // bump
+
+    /// Whether `approver` (optionally joined by `co_approver`) may resolve the escrow.
+    pub fn can_resolve(&self, approver: &Pubkey, co_approver: Option<&Pubkey>) -> bool {
+        if *approver == self.arbiter {
+            return true;
+        }
+        match co_approver {
+            Some(co) => {
+                (*approver == self.sender && *co == self.recipient)
+                    || (*approver == self.recipient && *co == self.sender)
+            }
+            None => false,
+        }
+    }
+}
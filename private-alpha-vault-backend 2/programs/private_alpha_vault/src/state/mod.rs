@@ -1,7 +1,31 @@
 pub mod vault;
 pub mod user_position;
 pub mod stealth_note;
+pub mod stream;
+pub mod time_lock;
+pub mod scheduled_transfer;
+pub mod escrow;
+pub mod subscription;
+pub mod note_claim_receipt;
+pub mod stealth_meta_address;
+pub mod note_batch;
+pub mod token_note;
+pub mod vault_stake;
+pub mod strategy;
+pub mod yield_history;
 
 pub use vault::*;
 pub use user_position::*;
 pub use stealth_note::*;
+pub use stream::*;
+pub use time_lock::*;
+pub use scheduled_transfer::*;
+pub use escrow::*;
+pub use subscription::*;
+pub use note_claim_receipt::*;
+pub use stealth_meta_address::*;
+pub use note_batch::*;
+pub use token_note::*;
+pub use vault_stake::*;
+pub use strategy::*;
+pub use yield_history::*;
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use inco_lightning::types::Euint128;
+
+use super::TimeLock;
+use crate::constants::NOTE_BATCH_LEAF_DOMAIN;
+
+/// Many same-denomination stealth notes issued in one account (gift cards,
+/// airdrops). Instead of one PDA per note, the batch commits to its note ids
+/// with a Merkle root and tracks claims in a bitmap.
+///
+/// Leaf `i` is `sha256(NOTE_BATCH_LEAF_DOMAIN || i as u32 LE || note_id_i)`,
+/// where `note_id_i = derive_note_id(vault, salt, secret_i)`. Inner nodes are
+/// `sha256(left || right)`, ordered by the leaf index bits.
+///
+/// Once `expires_at` passes, claims stop and the sender can take back the
+/// unclaimed notes with `reclaim_note_batch`.
+#[account]
+pub struct NoteBatch {
+    /// Wallet that funded the batch
+    pub sender: Pubkey,
+    /// Caller-chosen id so a sender can issue several batches
+    pub batch_id: u64,
+    /// Merkle root over the batch's leaves
+    pub merkle_root: [u8; 32],
+    /// Salt mixed into every note id of the batch
+    pub salt: [u8; 32],
+    /// Encrypted amount credited per claimed note
+    pub encrypted_denomination: Euint128,
+    /// Lamports paid out per claimed note
+    pub denomination_lamports: u64,
+    /// Number of notes in the batch
+    pub leaf_count: u32,
+    /// Number of notes claimed so far
+    pub claimed_count: u32,
+    /// Timestamp when the batch was created
+    pub created_at: i64,
+    /// PDA bump seed
+    pub bump: u8,
+    /// After this, notes can no longer be claimed and the sender may reclaim the rest
+    pub expires_at: TimeLock,
+    /// One bit per leaf, set once that note is claimed
+    pub claimed_bitmap: Vec<u8>,
+}
+
+impl NoteBatch {
+    pub const BASE_SIZE: usize = 8 + // discriminator
+        32 +  // sender
+        8 +   // batch_id
+        32 +  // merkle_root
+        32 +  // salt
+        16 +  // Euint128 handle (128-bit)
+        8 +   // denomination_lamports
+        4 +   // leaf_count
+        4 +   // claimed_count
+        8 +   // created_at
+        1 +   // bump
+        TimeLock::SIZE + // expires_at
+        4;    // claimed_bitmap length prefix
+
+    /// Bytes of bitmap needed for `leaf_count` notes.
+    pub fn bitmap_len(leaf_count: u32) -> usize {
+        (leaf_count as usize).div_ceil(8)
+    }
+
+    /// Account size for a batch of `leaf_count` notes.
+    pub fn size(leaf_count: u32) -> usize {
+        Self::BASE_SIZE + Self::bitmap_len(leaf_count)
+    }
+
+    pub fn is_claimed(&self, index: u32) -> bool {
+        self.claimed_bitmap[index as usize / 8] & (1 << (index % 8)) != 0
+    }
+
+    pub fn set_claimed(&mut self, index: u32) {
+        self.claimed_bitmap[index as usize / 8] |= 1 << (index % 8);
+    }
+
+    /// Lamports still held for notes nobody has claimed.
+    pub fn unclaimed_lamports(&self) -> Result<u64> {
+        ((self.leaf_count - self.claimed_count) as u64)
+            .checked_mul(self.denomination_lamports)
+            .ok_or_else(|| crate::errors::VaultError::Overflow.into())
+    }
+
+    /// Whether `note_id` is leaf `index` of the batch, given its Merkle `proof`.
+    pub fn verify_leaf(&self, index: u32, note_id: &[u8; 32], proof: &[[u8; 32]]) -> bool {
+        let mut node = hashv(&[NOTE_BATCH_LEAF_DOMAIN, &index.to_le_bytes(), note_id]).to_bytes();
+        let mut position = index;
+        for sibling in proof {
+            node = if position & 1 == 0 {
+                hashv(&[&node, sibling]).to_bytes()
+            } else {
+                hashv(&[sibling, &node]).to_bytes()
+            };
+            position >>= 1;
+        }
+        position == 0 && node == self.merkle_root
+    }
+}
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+/// Marks that `claimer` took a share of a split stealth note, so each wallet
+/// can claim a given note at most once.
+#[account]
+pub struct NoteClaimReceipt {
+    /// Stealth note the share was taken from
+    pub note: Pubkey,
+    /// Wallet that claimed the share
+    pub claimer: Pubkey,
+    /// Lamports received
+    pub lamports: u64,
+    /// Timestamp of the claim
+    pub claimed_at: i64,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl NoteClaimReceipt {
+    pub const SIZE: usize = 8 + // discriminator
+        32 +  // note
+        32 +  // claimer
+        8 +   // lamports
+        8 +   // claimed_at
+        1;    // bump
+}
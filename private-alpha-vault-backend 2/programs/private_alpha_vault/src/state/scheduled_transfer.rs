@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use inco_lightning::types::Euint128;
+
+use super::TimeLock;
+
+/// A transfer queued by the sender that anyone can execute once
+/// `execute_after` has passed. The amount is escrowed here at creation.
+#[account]
+pub struct ScheduledTransfer {
+    /// Position owner that queued the transfer
+    pub sender: Pubkey,
+    /// Owner of the position receiving the transfer
+    pub recipient: Pubkey,
+    /// Caller-chosen id so a sender can queue several transfers
+    pub transfer_id: u64,
+    /// Encrypted amount debited from the sender at creation
+    pub encrypted_amount: Euint128,
+    /// Real SOL lamports escrowed in this PDA
+    pub lamports: u64,
+    /// Earliest timestamp or slot at which the transfer can execute
+    pub execute_after: TimeLock,
+    /// Timestamp when the transfer was queued
+    pub created_at: i64,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl ScheduledTransfer {
+    pub const SIZE: usize = 8 + // discriminator
+        32 +  // sender
+        32 +  // recipient
+        8 +   // transfer_id
+        16 +  // Euint128 handle (128-bit)
+        8 +   // lamports
+        TimeLock::SIZE + // execute_after
+        8 +   // created_at
+        1;    // bump
+}
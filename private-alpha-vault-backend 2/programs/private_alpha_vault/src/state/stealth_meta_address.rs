@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+/// A recipient's published stealth meta-address.
+/// Senders combine it with a fresh ephemeral key to derive a one-time claim
+/// key for each note, so no secret has to be exchanged off-chain and notes
+/// to the same recipient are unlinkable on-chain.
+#[account]
+pub struct StealthMetaAddress {
+    /// Wallet that published (and may update) this meta-address
+    pub owner: Pubkey,
+    /// Ed25519 public key used for ECDH when scanning for notes
+    pub scan_pubkey: [u8; 32],
+    /// Ed25519 public key the one-time claim keys are derived from
+    pub spend_pubkey: [u8; 32],
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl StealthMetaAddress {
+    pub const SIZE: usize = 8 + // discriminator
+        32 +  // owner
+        32 +  // scan_pubkey
+        32 +  // spend_pubkey
+        1;    // bump
+}
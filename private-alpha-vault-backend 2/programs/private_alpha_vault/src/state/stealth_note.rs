@@ -1,6 +1,55 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::{hash, hashv};
 use inco_lightning::types::Euint128;
 
+use super::{FeeKind, TimeLock};
+use crate::constants::{NOTE_ID_DOMAIN, NOTE_ID_VERSION};
+
+/// How the funds of a stealth note can be claimed.
+/// The zero tag (`Single`) is what notes created before claim modes existed decode to.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum NoteClaimMode {
+    /// One claimer takes everything
+    Single,
+    /// Up to `max_claims` distinct claimers each take an equal share
+    /// (the last one also takes the rounding dust)
+    Split { max_claims: u16 },
+    /// Claimers withdraw any amount until the note is empty
+    Partial,
+}
+
+impl NoteClaimMode {
+    pub const SIZE: usize = 1 + // variant tag
+        2;    // largest payload (u16)
+}
+
+/// Creation-time options for `create_stealth_note_with_options`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct NoteOptions {
+    pub claim_mode: NoteClaimMode,
+    /// Key whose signature authorizes relayed claims (see `relayed_claim_stealth_note`).
+    /// Clients derive it from the note secret; `None` disables relayed claims.
+    pub claim_authority: Option<Pubkey>,
+    /// Sender's ephemeral public key for notes addressed to a stealth
+    /// meta-address; `claim_authority` must then be the derived one-time key.
+    pub ephemeral_pubkey: Option<[u8; 32]>,
+    /// Per-note salt for the note id (see `derive_note_id`)
+    pub salt: [u8; 32],
+    /// Lets the sender revoke the note until this time lock expires
+    pub revocable_until: Option<TimeLock>,
+    /// Client-encrypted memo published with the note (see `EncryptedMemo`)
+    pub memo: Option<Vec<u8>>,
+}
+
+/// Salted, domain-separated note id:
+/// `sha256(NOTE_ID_DOMAIN || program_id || vault || salt || secret)`.
+/// Binding the program and vault keeps the same secret from mapping to the same
+/// note across deployments, and the salt defeats precomputed passphrase tables.
+/// It does not make a weak passphrase strong: the salt is public on the note.
+pub fn derive_note_id(vault: &Pubkey, salt: &[u8; 32], secret: &[u8]) -> [u8; 32] {
+    hashv(&[NOTE_ID_DOMAIN, crate::ID.as_ref(), vault.as_ref(), salt, secret]).to_bytes()
+}
+
 /// A stealth note allows sending funds to a secret identifier (hash) instead of
 /// a public wallet address. The recipient can claim by proving knowledge of the
 /// secret that hashes to the note_id.
@@ -21,6 +70,28 @@ pub struct StealthNote {
     pub claimed: bool,
     /// PDA bump seed
     pub bump: u8,
+    /// How the note can be claimed
+    pub claim_mode: NoteClaimMode,
+    /// Number of claims made so far (split and partial notes)
+    pub claims_made: u16,
+    /// Lamports escrowed at creation; `lamports` tracks what remains
+    pub original_lamports: u64,
+    /// Ed25519 key that can authorize relayed claims; default pubkey if disabled
+    pub claim_authority: Pubkey,
+    /// Ephemeral ECDH public key for meta-address notes; zero otherwise.
+    /// Recipients scan these to find notes addressed to them.
+    pub ephemeral_pubkey: [u8; 32],
+    /// How `note_id` was derived from the secret (0 = legacy `hash(secret)`)
+    pub id_version: u8,
+    /// Salt mixed into the note id (id_version 1)
+    pub salt: [u8; 32],
+    /// Whether the sender may revoke the note (see `revocable_until`)
+    pub revocable: bool,
+    /// The sender can revoke until this lock expires; ignored unless `revocable`
+    pub revocable_until: TimeLock,
+    /// Whether the note was funded from the sender's vault position rather
+    /// than their wallet (false for notes from before this was recorded)
+    pub funded_from_position: bool,
 }
 
 impl StealthNote {
@@ -31,5 +102,59 @@ impl StealthNote {
         32 +  // sender
         8 +   // created_at
         1 +   // claimed
-        1;    // bump
+        1 +   // bump
+        NoteClaimMode::SIZE + // claim_mode
+        2 +   // claims_made
+        8 +   // original_lamports
+        32 +  // claim_authority
+        32 +  // ephemeral_pubkey
+        1 +   // id_version
+        32 +  // salt
+        1 +   // revocable
+        TimeLock::SIZE + // revocable_until
+        1;    // funded_from_position
+
+    /// Record the id scheme for a new note. New notes always use the salted
+    /// id; version 0 only survives on notes created before it existed.
+    pub fn set_id_scheme(&mut self, salt: [u8; 32]) {
+        self.id_version = NOTE_ID_VERSION;
+        self.salt = salt;
+    }
+
+    /// Whether `secret` is the preimage of this note's id under its id scheme.
+    pub fn verify_secret(&self, vault: &Pubkey, secret: &[u8]) -> bool {
+        let expected = match self.id_version {
+            0 => hash(secret).to_bytes(),
+            NOTE_ID_VERSION => derive_note_id(vault, &self.salt, secret),
+            _ => return false,
+        };
+        expected == self.note_id
+    }
+
+    /// Whether the sender can still revoke the note as of `clock`.
+    pub fn can_revoke(&self, clock: &Clock) -> bool {
+        self.revocable && !self.revocable_until.is_unlocked(clock)
+    }
+
+    /// Fee charged when the note's funds leave it for a position
+    /// (`to_position`) or a wallet. Position funds moving to another position
+    /// are a transfer and to a wallet a withdrawal; wallet funds entering a
+    /// position are a deposit. Wallet to wallet never touches the vault.
+    pub fn payout_fee_kind(&self, to_position: bool) -> Option<FeeKind> {
+        match (self.funded_from_position, to_position) {
+            (true, true) => Some(FeeKind::Transfer),
+            (true, false) => Some(FeeKind::Withdraw),
+            (false, true) => Some(FeeKind::Deposit),
+            (false, false) => None,
+        }
+    }
+
+    /// Lamports the next claimer of a split note receives.
+    pub fn next_split_share(&self, max_claims: u16) -> u64 {
+        if self.claims_made + 1 >= max_claims {
+            self.lamports
+        } else {
+            self.original_lamports / max_claims as u64
+        }
+    }
 }
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+/// A registered external yield source. The strategy program implements the
+/// small CPI interface in `strategy_utils` and holds the allocated SOL in
+/// `strategy_state`, an account it owns.
+#[account]
+pub struct Strategy {
+    /// Program implementing the strategy interface
+    pub strategy_program: Pubkey,
+    /// Strategy-owned account holding the vault's allocation
+    pub strategy_state: Pubkey,
+    /// Share of total escrow (basis points) this strategy may hold
+    pub target_weight_bps: u16,
+    /// Principal currently allocated to the strategy
+    pub deployed_lamports: u64,
+    /// Value reported by the strategy at the last harvest
+    pub last_reported_value: u64,
+    /// Timestamp of the last harvest
+    pub last_harvest_at: i64,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl Strategy {
+    pub const SIZE: usize = 8 + // discriminator
+        32 +  // strategy_program
+        32 +  // strategy_state
+        2 +   // target_weight_bps
+        8 +   // deployed_lamports
+        8 +   // last_reported_value
+        8 +   // last_harvest_at
+        1;    // bump
+
+    /// Most this strategy may hold given `total_escrow` lamports of escrow.
+    pub fn allocation_cap(&self, total_escrow: u64) -> u64 {
+        ((total_escrow as u128) * (self.target_weight_bps as u128) / 10_000) as u64
+    }
+}
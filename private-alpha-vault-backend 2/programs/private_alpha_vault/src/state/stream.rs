@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+/// A continuous payment from the sender's vault position to a recipient.
+/// The full amount is escrowed in this PDA at creation and accrues linearly
+/// between `start_time` and `end_time`.
+#[account]
+pub struct Stream {
+    /// Position owner funding the stream
+    pub sender: Pubkey,
+    /// Owner of the position receiving the stream
+    pub recipient: Pubkey,
+    /// Caller-chosen id so a sender can run several streams to one recipient
+    pub stream_id: u64,
+    /// Accrual rate in lamports per second
+    pub rate_lamports_per_second: u64,
+    /// Unix timestamp when accrual starts
+    pub start_time: i64,
+    /// Unix timestamp when accrual stops
+    pub end_time: i64,
+    /// Real SOL lamports escrowed in this stream PDA at creation
+    pub deposited_lamports: u64,
+    /// Lamports already pulled by the recipient
+    pub withdrawn_lamports: u64,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl Stream {
+    pub const SIZE: usize = 8 + // discriminator
+        32 +  // sender
+        32 +  // recipient
+        8 +   // stream_id
+        8 +   // rate_lamports_per_second
+        8 +   // start_time
+        8 +   // end_time
+        8 +   // deposited_lamports
+        8 +   // withdrawn_lamports
+        1;    // bump
+
+    /// Total lamports accrued to the recipient as of `now`, withdrawn or not.
+    pub fn accrued_at(&self, now: i64) -> u64 {
+        let elapsed = now.clamp(self.start_time, self.end_time) - self.start_time;
+        (elapsed as u64)
+            .saturating_mul(self.rate_lamports_per_second)
+            .min(self.deposited_lamports)
+    }
+
+    /// Lamports the recipient can pull as of `now`.
+    pub fn withdrawable_at(&self, now: i64) -> u64 {
+        self.accrued_at(now).saturating_sub(self.withdrawn_lamports)
+    }
+}
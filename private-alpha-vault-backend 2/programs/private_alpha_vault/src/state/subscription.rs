@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use inco_lightning::types::Euint128;
+
+/// Recurring payment authorized once by the payer and pulled from their
+/// position by anyone once per period.
+#[account]
+pub struct Subscription {
+    /// Position owner being billed
+    pub payer: Pubkey,
+    /// Owner of the position receiving each charge
+    pub merchant: Pubkey,
+    /// Caller-chosen id so a payer can hold several subscriptions per merchant
+    pub subscription_id: u64,
+    /// Encrypted per-period amount
+    pub encrypted_amount: Euint128,
+    /// Real SOL lamports moved per period
+    pub lamports: u64,
+    /// Seconds between charges
+    pub period_seconds: i64,
+    /// Unix timestamp at which the next charge becomes due
+    pub next_due: i64,
+    /// Maximum number of charges (0 = unlimited)
+    pub max_periods: u32,
+    /// Number of charges made so far
+    pub periods_charged: u32,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl Subscription {
+    pub const SIZE: usize = 8 + // discriminator
+        32 +  // payer
+        32 +  // merchant
+        8 +   // subscription_id
+        16 +  // Euint128 handle (128-bit)
+        8 +   // lamports
+        8 +   // period_seconds
+        8 +   // next_due
+        4 +   // max_periods
+        4 +   // periods_charged
+        1;    // bump
+
+    pub fn is_exhausted(&self) -> bool {
+        self.max_periods != 0 && self.periods_charged >= self.max_periods
+    }
+}
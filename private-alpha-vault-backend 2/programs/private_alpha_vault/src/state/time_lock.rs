@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+/// Point in time after which a time-locked action becomes available,
+/// expressed either as a unix timestamp or as a slot.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TimeLock {
+    UnixTimestamp(i64),
+    Slot(u64),
+}
+
+impl TimeLock {
+    pub const SIZE: usize = 1 + // variant tag
+        8;    // i64 / u64 payload
+
+    /// Whether the lock has expired as of `clock`.
+    pub fn is_unlocked(&self, clock: &Clock) -> bool {
+        match *self {
+            TimeLock::UnixTimestamp(ts) => clock.unix_timestamp >= ts,
+            TimeLock::Slot(slot) => clock.slot >= slot,
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use super::{derive_note_id, TimeLock};
+use crate::constants::NOTE_ID_VERSION;
+
+/// A stealth note denominated in an SPL / Token-2022 mint.
+/// The tokens sit in an associated token account owned by this PDA and are
+/// paid straight to the claimer's associated token account. Token transfers
+/// are public, so unlike SOL notes only the recipient is hidden, not the amount.
+#[account]
+pub struct TokenNote {
+    /// Note id, derived from the secret like `StealthNote::note_id`
+    pub note_id: [u8; 32],
+    /// Mint of the escrowed tokens
+    pub mint: Pubkey,
+    /// Token program owning the mint (SPL Token or Token-2022)
+    pub token_program: Pubkey,
+    /// Token amount escrowed (after any Token-2022 transfer fee)
+    pub amount: u64,
+    /// The sender's pubkey; receives the account rent back on claim
+    pub sender: Pubkey,
+    /// Timestamp when the note was created
+    pub created_at: i64,
+    /// Whether the note has been claimed
+    pub claimed: bool,
+    /// PDA bump seed
+    pub bump: u8,
+    /// How `note_id` was derived from the secret; always `NOTE_ID_VERSION`,
+    /// token notes never used the unsalted id
+    pub id_version: u8,
+    /// Salt mixed into the note id
+    pub salt: [u8; 32],
+    /// Whether the sender may revoke the note (see `revocable_until`)
+    pub revocable: bool,
+    /// The sender can revoke until this lock expires; ignored unless `revocable`
+    pub revocable_until: TimeLock,
+}
+
+impl TokenNote {
+    pub const SIZE: usize = 8 + // discriminator
+        32 +  // note_id
+        32 +  // mint
+        32 +  // token_program
+        8 +   // amount
+        32 +  // sender
+        8 +   // created_at
+        1 +   // claimed
+        1 +   // bump
+        1 +   // id_version
+        32 +  // salt
+        1 +   // revocable
+        TimeLock::SIZE; // revocable_until
+
+    /// Whether `secret` is the preimage of this note's salted id.
+    pub fn verify_secret(&self, vault: &Pubkey, secret: &[u8]) -> bool {
+        self.id_version == NOTE_ID_VERSION && derive_note_id(vault, &self.salt, secret) == self.note_id
+    }
+
+    /// Whether the sender can still revoke the note as of `clock`.
+    pub fn can_revoke(&self, clock: &Clock) -> bool {
+        self.revocable && !self.revocable_until.is_unlocked(clock)
+    }
+}
@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
 use inco_lightning::types::Euint128;
 
+use crate::errors::VaultError;
+
 #[account]
 pub struct UserPosition {
     /// Owner of this position
@@ -11,12 +13,73 @@ pub struct UserPosition {
     pub last_yield_index: u128,
     /// PDA bump seed
     pub bump: u8,
+    /// Plaintext principal this position owns in the vault's pooled escrow.
+    /// Needed for yield accounting and withdrawal checks; the SOL itself
+    /// lives in the vault PDA. This is public, so the position's value is
+    /// not hidden by the encrypted balance.
+    pub escrow_lamports: u64,
+    /// Index into `LOCK_TIERS` plus one; 0 when not locked
+    pub lock_tier: u8,
+    /// When the lock expires and principal can leave the position again
+    pub unlock_at: i64,
+    /// Extra yield weight on top of `escrow_lamports` from the lock multiplier
+    pub boost_weight: u64,
+    /// Last loss index observed by this position (fixed-point, scaled by 1e12).
+    /// New positions start at 0, which is harmless: without principal their
+    /// first settlement takes no loss.
+    pub last_loss_index: u128,
+    /// When pending yield was last settled into this position; 0 on positions
+    /// that have not settled since this field was added
+    pub last_settled_at: i64,
+}
+
+/// A lockup option: principal stays in the position for `duration` seconds
+/// and earns yield on `multiplier_bps` of itself; leaving early costs
+/// `early_exit_penalty_bps` of principal.
+pub struct LockTier {
+    pub duration: i64,
+    pub multiplier_bps: u16,
+    pub early_exit_penalty_bps: u16,
 }
 
+pub const LOCK_TIERS: [LockTier; 3] = [
+    LockTier { duration: 30 * 24 * 60 * 60, multiplier_bps: 11_000, early_exit_penalty_bps: 200 },
+    LockTier { duration: 90 * 24 * 60 * 60, multiplier_bps: 12_500, early_exit_penalty_bps: 500 },
+    LockTier { duration: 365 * 24 * 60 * 60, multiplier_bps: 15_000, early_exit_penalty_bps: 1_000 },
+];
+
 impl UserPosition {
     pub const SIZE: usize = 8 + // discriminator
         32 +  // owner
         16 +  // Euint128 handle (128-bit)
         16 +  // last_yield_index
-        1;    // bump
+        1 +   // bump
+        8 +   // escrow_lamports
+        1 +   // lock_tier
+        8 +   // unlock_at
+        8 +   // boost_weight
+        16 +  // last_loss_index
+        8;    // last_settled_at
+
+    /// Size of positions created before escrow was pooled in the vault.
+    pub const LEGACY_SIZE: usize = 8 + 32 + 16 + 16 + 1;
+
+    /// Lock tier in effect, if any.
+    pub fn lock(&self) -> Option<&'static LockTier> {
+        self.lock_tier
+            .checked_sub(1)
+            .and_then(|i| LOCK_TIERS.get(i as usize))
+    }
+
+    /// Whether principal is still locked at `now`.
+    pub fn is_locked(&self, now: i64) -> bool {
+        self.lock().is_some() && now < self.unlock_at
+    }
+
+    /// Weight this position's yield share is computed on.
+    pub fn yield_weight(&self) -> Result<u64> {
+        self.escrow_lamports
+            .checked_add(self.boost_weight)
+            .ok_or_else(|| VaultError::Overflow.into())
+    }
 }
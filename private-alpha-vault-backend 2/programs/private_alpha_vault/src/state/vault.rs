@@ -1,18 +1,106 @@
 use anchor_lang::prelude::*;
 use inco_lightning::types::Euint128;
 
+use crate::constants::MAX_FEE_TIERS;
+use crate::errors::VaultError;
+
 #[account]
 pub struct Vault {
     /// Authority that can apply yield
     pub authority: Pubkey,
     /// Encrypted total balance (handle to off-chain encrypted value)
     pub total_encrypted_balance: Euint128,
-    /// Total escrowed SOL across all user PDAs (lamports)
+    /// Total escrowed SOL owned by all positions (lamports).
+    /// Held in this PDA's pool alongside undistributed yield.
     pub total_escrow_lamports: u64,
     /// Global yield index (fixed-point, scaled by 1e12)
     pub yield_index: u128,
     /// PDA bump seed
     pub bump: u8,
+    /// Share of total escrow (basis points) that may be delegated to stake
+    /// accounts; the rest stays liquid in this PDA for withdrawals
+    pub stake_target_bps: u16,
+    /// Principal currently delegated to stake accounts (including harvested
+    /// rewards). Counts towards the pool alongside this PDA's lamports.
+    pub staked_lamports: u64,
+    /// Id for the next stake account PDA
+    pub next_stake_id: u64,
+    /// Principal currently allocated to yield strategies
+    pub strategy_lamports: u64,
+    /// Sum of all registered strategies' target weights (basis points)
+    pub strategy_weight_bps: u16,
+    /// Yield sitting in the pool that has not yet vested into `yield_index`
+    pub vesting_lamports: u64,
+    /// When `vesting_lamports` will have fully vested
+    pub vesting_end: i64,
+    /// Last time vested yield was folded into `yield_index`
+    pub last_vest_at: i64,
+    /// Part of distributed yield (scaled by 1e12) too small to move
+    /// `yield_index`; carried into the next distribution
+    pub yield_remainder: u128,
+    /// Yield in `yield_index` not yet settled into positions (scaled by 1e12)
+    pub owed_yield: u128,
+    /// Share of each yield distribution (basis points) taken as protocol fee
+    pub performance_fee_bps: u16,
+    /// Wallet allowed to collect accrued fees
+    pub fee_recipient: Pubkey,
+    /// Fees sitting in the pool, not yet collected
+    pub accrued_fees: u64,
+    /// Fee (basis points) on deposits below the first tier
+    pub deposit_fee_bps: u16,
+    /// Fee (basis points) on withdrawals below the first tier
+    pub withdraw_fee_bps: u16,
+    /// Fee (basis points) on transfers below the first tier
+    pub transfer_fee_bps: u16,
+    /// Number of entries in `fee_tiers` in use
+    pub fee_tier_count: u8,
+    /// Amount tiers overriding the base fees, ascending by `min_lamports`
+    pub fee_tiers: [FeeTier; MAX_FEE_TIERS],
+    /// Sum of all positions' `boost_weight`; yield is spread over
+    /// `total_escrow_lamports + total_boost_weight`
+    pub total_boost_weight: u64,
+    /// Most paid from accrued fees to `settle_positions` cranks per position;
+    /// the reward is `CRANK_REWARD_BPS` of the yield settled
+    pub crank_reward_lamports: u64,
+    /// Principal still held by position PDAs from before escrow was pooled.
+    /// Counted in `total_escrow_lamports` but not in the pool until
+    /// `migrate_position` moves it there.
+    pub unmigrated_principal: u64,
+    /// Realised losses per lamport of principal (fixed-point, scaled by 1e12)
+    pub loss_index: u128,
+    /// Losses in `loss_index` not yet taken from positions (scaled by 1e12)
+    pub unsettled_loss: u128,
+    /// Set on vaults migrated from before `owed_yield` was tracked; yield
+    /// booked back then is missing from it, so settling floors at zero
+    pub owed_yield_untracked: bool,
+}
+
+/// Fees for amounts of at least `min_lamports`, up to the next tier.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct FeeTier {
+    /// Smallest amount (lamports) this tier applies to
+    pub min_lamports: u64,
+    /// Fee (basis points) on deposits in this tier
+    pub deposit_fee_bps: u16,
+    /// Fee (basis points) on withdrawals in this tier
+    pub withdraw_fee_bps: u16,
+    /// Fee (basis points) on transfers in this tier
+    pub transfer_fee_bps: u16,
+}
+
+impl FeeTier {
+    pub const SIZE: usize = 8 + // min_lamports
+        2 +   // deposit_fee_bps
+        2 +   // withdraw_fee_bps
+        2;    // transfer_fee_bps
+}
+
+/// Operation an entry/exit fee is charged on.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum FeeKind {
+    Deposit,
+    Withdraw,
+    Transfer,
 }
 
 impl Vault {
@@ -21,5 +109,62 @@ impl Vault {
         16 +  // Euint128 handle (128-bit)
         8 +   // total_escrow_lamports
         16 +  // yield_index
-        1;    // bump
+        1 +   // bump
+        2 +   // stake_target_bps
+        8 +   // staked_lamports
+        8 +   // next_stake_id
+        8 +   // strategy_lamports
+        2 +   // strategy_weight_bps
+        8 +   // vesting_lamports
+        8 +   // vesting_end
+        8 +   // last_vest_at
+        16 +  // yield_remainder
+        16 +  // owed_yield
+        2 +   // performance_fee_bps
+        32 +  // fee_recipient
+        8 +   // accrued_fees
+        2 +   // deposit_fee_bps
+        2 +   // withdraw_fee_bps
+        2 +   // transfer_fee_bps
+        1 +   // fee_tier_count
+        MAX_FEE_TIERS * FeeTier::SIZE + // fee_tiers
+        8 +   // total_boost_weight
+        8 +   // crank_reward_lamports
+        8 +   // unmigrated_principal
+        16 +  // loss_index
+        16 +  // unsettled_loss
+        1;    // owed_yield_untracked
+
+    /// Size of vaults created before escrow was pooled.
+    pub const LEGACY_SIZE: usize = 8 + 32 + 16 + 8 + 16 + 1;
+
+    /// Size of vaults created before `owed_yield` was tracked: every field
+    /// up to `yield_remainder`.
+    pub const PRE_OWED_YIELD_SIZE: usize = 8 + 32 + 16 + 8 + 16 + 1 + 2 + 8 + 8 + 8 + 2 + 8 + 8 + 8 + 16;
+
+    /// Weight yield is spread over: escrow plus lock boosts.
+    pub fn total_yield_weight(&self) -> Result<u64> {
+        self.total_escrow_lamports
+            .checked_add(self.total_boost_weight)
+            .ok_or_else(|| VaultError::Overflow.into())
+    }
+
+    /// Fee on `lamports` for `kind`: the highest tier the amount reaches, or
+    /// the base fee below the first tier. Rounded up, so splitting an amount
+    /// into small pieces never gets it under the fee.
+    pub fn entry_exit_fee(&self, kind: FeeKind, lamports: u64) -> u64 {
+        let count = (self.fee_tier_count as usize).min(self.fee_tiers.len());
+        let (deposit_bps, withdraw_bps, transfer_bps) = self.fee_tiers[..count]
+            .iter()
+            .rev()
+            .find(|tier| lamports >= tier.min_lamports)
+            .map(|tier| (tier.deposit_fee_bps, tier.withdraw_fee_bps, tier.transfer_fee_bps))
+            .unwrap_or((self.deposit_fee_bps, self.withdraw_fee_bps, self.transfer_fee_bps));
+        let bps = match kind {
+            FeeKind::Deposit => deposit_bps,
+            FeeKind::Withdraw => withdraw_bps,
+            FeeKind::Transfer => transfer_bps,
+        };
+        ((lamports as u128) * (bps as u128)).div_ceil(10_000) as u64
+    }
 }
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+/// Book-keeping for one native stake account funded from the vault pool.
+/// The stake account is a PDA whose staker and withdrawer are the vault PDA.
+#[account]
+pub struct VaultStake {
+    /// Sequential id, used in the stake and record PDA seeds
+    pub stake_id: u64,
+    /// The stake account PDA
+    pub stake_account: Pubkey,
+    /// Validator vote account the stake is delegated to
+    pub vote_account: Pubkey,
+    /// Pool lamports delegated so far, including harvested rewards
+    pub principal_lamports: u64,
+    /// Rent-exempt reserve, paid by the authority and returned on withdrawal
+    pub rent_reserve: u64,
+    /// Epoch of the last reward harvest
+    pub last_harvest_epoch: u64,
+    /// Whether the stake has been deactivated
+    pub deactivating: bool,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl VaultStake {
+    pub const SIZE: usize = 8 + // discriminator
+        8 +   // stake_id
+        32 +  // stake_account
+        32 +  // vote_account
+        8 +   // principal_lamports
+        8 +   // rent_reserve
+        8 +   // last_harvest_epoch
+        1 +   // deactivating
+        1;    // bump
+
+    /// Lamports in `stake_account` beyond the reserve and booked principal.
+    pub fn unbooked_rewards(&self, stake_account_lamports: u64) -> u64 {
+        stake_account_lamports
+            .saturating_sub(self.rent_reserve)
+            .saturating_sub(self.principal_lamports)
+    }
+}
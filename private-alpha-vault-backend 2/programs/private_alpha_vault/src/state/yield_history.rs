@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::YIELD_HISTORY_LEN;
+
+/// One yield distribution, as booked by `distribute_yield`.
+#[zero_copy]
+#[derive(Default)]
+pub struct YieldRecord {
+    /// `Vault::yield_index` right after the distribution. Yield that vests
+    /// later moves the index further.
+    pub yield_index_after: u128,
+    /// When the distribution was booked
+    pub timestamp: i64,
+    /// Yield going to positions, after the performance fee (lamports)
+    pub lamports: u64,
+    /// Performance fee taken from it (lamports)
+    pub fee_lamports: u64,
+    /// Total escrow the yield was spread over (lamports)
+    pub total_escrow_lamports: u64,
+}
+
+/// Ring buffer of the last `YIELD_HISTORY_LEN` yield distributions, so
+/// clients can compute APY and audit payouts. Zero-copy: it is too large to
+/// deserialize on every distribution.
+#[account(zero_copy)]
+pub struct YieldHistory {
+    /// Slot the next record is written to
+    pub head: u64,
+    /// Records written so far, capped at `YIELD_HISTORY_LEN`
+    pub count: u64,
+    pub records: [YieldRecord; YIELD_HISTORY_LEN],
+}
+
+impl YieldHistory {
+    pub const SIZE: usize = 8 + std::mem::size_of::<YieldHistory>();
+
+    /// Append `record`, overwriting the oldest once the buffer is full.
+    pub fn push(&mut self, record: YieldRecord) {
+        let head = self.head as usize % YIELD_HISTORY_LEN;
+        self.records[head] = record;
+        self.head = ((head + 1) % YIELD_HISTORY_LEN) as u64;
+        self.count = (self.count + 1).min(YIELD_HISTORY_LEN as u64);
+    }
+}
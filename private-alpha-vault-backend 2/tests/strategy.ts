@@ -0,0 +1,237 @@
+import * as anchor from "@coral-xyz/anchor";
+import { AnchorError, BN, Program } from "@coral-xyz/anchor";
+import { LAMPORTS_PER_SOL, PublicKey, SystemProgram } from "@solana/web3.js";
+import { encryptValue } from "@inco/solana-sdk/encryption";
+import { hexToBuffer } from "@inco/solana-sdk/utils";
+import { expect } from "chai";
+import { PrivateAlphaVault } from "../target/types/private_alpha_vault";
+import { MockStrategy } from "../target/types/mock_strategy";
+
+const INCO_LIGHTNING_ID = new PublicKey("5sjEbPiqgZrYwR31ahR6Uk9wf5awoX61YGg7jExQSwaj");
+
+const DEPOSIT = new BN(2 * LAMPORTS_PER_SOL);
+// 50% of a 2 SOL escrow caps the strategy at 1 SOL.
+const TARGET_WEIGHT_BPS = 5_000;
+const ALLOCATION = new BN(LAMPORTS_PER_SOL / 2);
+const GAIN = new BN(LAMPORTS_PER_SOL / 5);
+
+async function expectVaultError(promise: Promise<unknown>, code: string) {
+  try {
+    await promise;
+  } catch (err) {
+    expect(err).to.be.instanceOf(AnchorError);
+    expect((err as AnchorError).error.errorCode.code).to.equal(code);
+    return;
+  }
+  expect.fail(`expected ${code}`);
+}
+
+describe("mock strategy", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const vaultProgram = anchor.workspace.PrivateAlphaVault as Program<PrivateAlphaVault>;
+  const mockProgram = anchor.workspace.MockStrategy as Program<MockStrategy>;
+  const authority = provider.wallet.publicKey;
+
+  const [vault] = PublicKey.findProgramAddressSync([Buffer.from("vault_v2")], vaultProgram.programId);
+  const [yieldHistory] = PublicKey.findProgramAddressSync(
+    [Buffer.from("yield_history")],
+    vaultProgram.programId
+  );
+  const [userPosition] = PublicKey.findProgramAddressSync(
+    [Buffer.from("user_v2"), authority.toBuffer()],
+    vaultProgram.programId
+  );
+  const [strategy] = PublicKey.findProgramAddressSync(
+    [Buffer.from("strategy"), mockProgram.programId.toBuffer()],
+    vaultProgram.programId
+  );
+  const [strategySigner] = PublicKey.findProgramAddressSync(
+    [Buffer.from("strategy_signer"), mockProgram.programId.toBuffer()],
+    vaultProgram.programId
+  );
+  const [strategyState] = PublicKey.findProgramAddressSync(
+    [Buffer.from("mock_strategy"), vault.toBuffer()],
+    mockProgram.programId
+  );
+
+  const strategyAccounts = {
+    vault,
+    strategy,
+    strategyProgram: mockProgram.programId,
+    strategyState,
+    strategySigner,
+    authority,
+  };
+
+  before(async () => {
+    await vaultProgram.methods
+      .initializeVault()
+      .accountsPartial({
+        vault,
+        authority,
+        systemProgram: SystemProgram.programId,
+        incoLightningProgram: INCO_LIGHTNING_ID,
+      })
+      .rpc();
+
+    await vaultProgram.methods
+      .initializeYieldHistory()
+      .accountsPartial({ vault, yieldHistory, authority, systemProgram: SystemProgram.programId })
+      .rpc();
+
+    const encrypted = hexToBuffer(await encryptValue(BigInt(DEPOSIT.toString())));
+    await vaultProgram.methods
+      .deposit(encrypted, DEPOSIT)
+      .accountsPartial({
+        vault,
+        userPosition,
+        user: authority,
+        systemProgram: SystemProgram.programId,
+        incoLightningProgram: INCO_LIGHTNING_ID,
+      })
+      .rpc();
+
+    await mockProgram.methods
+      .initialize()
+      .accountsPartial({
+        state: strategyState,
+        vault,
+        signer: strategySigner,
+        payer: authority,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  });
+
+  it("registers the strategy", async () => {
+    await vaultProgram.methods
+      .registerStrategy(TARGET_WEIGHT_BPS)
+      .accountsPartial({
+        vault,
+        strategy,
+        strategyProgram: mockProgram.programId,
+        strategyState,
+        authority,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const registered = await vaultProgram.account.strategy.fetch(strategy);
+    expect(registered.strategyProgram.toBase58()).to.equal(mockProgram.programId.toBase58());
+    expect(registered.strategyState.toBase58()).to.equal(strategyState.toBase58());
+    expect(registered.targetWeightBps).to.equal(TARGET_WEIGHT_BPS);
+
+    const vaultAccount = await vaultProgram.account.vault.fetch(vault);
+    expect(vaultAccount.strategyWeightBps).to.equal(TARGET_WEIGHT_BPS);
+  });
+
+  it("allocates into the strategy", async () => {
+    const stateBefore = await provider.connection.getBalance(strategyState);
+
+    await vaultProgram.methods.allocateToStrategy(ALLOCATION).accountsPartial(strategyAccounts).rpc();
+
+    const stateAfter = await provider.connection.getBalance(strategyState);
+    expect(stateAfter - stateBefore).to.equal(ALLOCATION.toNumber());
+
+    const registered = await vaultProgram.account.strategy.fetch(strategy);
+    expect(registered.deployedLamports.eq(ALLOCATION)).to.be.true;
+    const vaultAccount = await vaultProgram.account.vault.fetch(vault);
+    expect(vaultAccount.strategyLamports.eq(ALLOCATION)).to.be.true;
+
+    const mockState = await mockProgram.account.mockStrategyState.fetch(strategyState);
+    expect(mockState.deposited.eq(ALLOCATION)).to.be.true;
+  });
+
+  it("rejects allocations above the target weight", async () => {
+    // Already holding 0.5 SOL of a 1 SOL cap.
+    const excess = new BN(LAMPORTS_PER_SOL / 2 + 1);
+    await expectVaultError(
+      vaultProgram.methods.allocateToStrategy(excess).accountsPartial(strategyAccounts).rpc(),
+      "StrategyWeightExceeded"
+    );
+  });
+
+  it("harvests a gain into the yield index", async () => {
+    // Simulate yield by sending lamports straight to the strategy's state.
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        SystemProgram.transfer({
+          fromPubkey: authority,
+          toPubkey: strategyState,
+          lamports: GAIN.toNumber(),
+        })
+      )
+    );
+
+    const before = await vaultProgram.account.vault.fetch(vault);
+
+    await vaultProgram.methods
+      .harvestStrategy()
+      .accountsPartial({
+        vault,
+        strategy,
+        strategyProgram: mockProgram.programId,
+        strategyState,
+        strategySigner,
+        cranker: authority,
+        incoLightningProgram: INCO_LIGHTNING_ID,
+        yieldHistory,
+      })
+      .rpc();
+
+    const harvested = await vaultProgram.account.vault.fetch(vault);
+    expect(harvested.vestingLamports.gt(before.vestingLamports)).to.be.true;
+    const registered = await vaultProgram.account.strategy.fetch(strategy);
+    expect(registered.deployedLamports.eq(ALLOCATION)).to.be.true;
+    expect(registered.lastReportedValue.eq(ALLOCATION)).to.be.true;
+
+    // Harvested yield vests over time; let some of it vest, then settle.
+    await new Promise((resolve) => setTimeout(resolve, 2_000));
+    await vaultProgram.methods
+      .settlePositions()
+      .accountsPartial({
+        vault,
+        cranker: authority,
+        systemProgram: SystemProgram.programId,
+        incoLightningProgram: INCO_LIGHTNING_ID,
+      })
+      .remainingAccounts([{ pubkey: userPosition, isSigner: false, isWritable: true }])
+      .rpc();
+
+    const settled = await vaultProgram.account.vault.fetch(vault);
+    expect(settled.yieldIndex.gt(before.yieldIndex)).to.be.true;
+  });
+
+  it("rejects a withdrawal that returns less than asked", async () => {
+    await mockProgram.methods
+      .setWithdrawShortfall(new BN(1))
+      .accountsPartial({ state: strategyState })
+      .rpc();
+
+    await expectVaultError(
+      vaultProgram.methods.deallocateFromStrategy(ALLOCATION).accountsPartial(strategyAccounts).rpc(),
+      "StrategyTransferMismatch"
+    );
+
+    await mockProgram.methods
+      .setWithdrawShortfall(new BN(0))
+      .accountsPartial({ state: strategyState })
+      .rpc();
+  });
+
+  it("deallocates from the strategy", async () => {
+    const vaultBefore = await provider.connection.getBalance(vault);
+
+    await vaultProgram.methods.deallocateFromStrategy(ALLOCATION).accountsPartial(strategyAccounts).rpc();
+
+    const vaultAfter = await provider.connection.getBalance(vault);
+    expect(vaultAfter - vaultBefore).to.equal(ALLOCATION.toNumber());
+
+    const registered = await vaultProgram.account.strategy.fetch(strategy);
+    expect(registered.deployedLamports.isZero()).to.be.true;
+    const vaultAccount = await vaultProgram.account.vault.fetch(vault);
+    expect(vaultAccount.strategyLamports.isZero()).to.be.true;
+  });
+});
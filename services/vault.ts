@@ -26,6 +26,13 @@ const SEED_VAULT = new TextEncoder().encode('vault_v2');
 const SEED_USER = new TextEncoder().encode('user_v2');
 
 const SEED_STEALTH_NOTE = new TextEncoder().encode('stealth_note');
+const SEED_YIELD_HISTORY = new TextEncoder().encode('yield_history');
+// Must match YIELD_HISTORY_LEN in the program's state/yield_history.rs
+const YIELD_HISTORY_LEN = 128;
+// Must match NOTE_ID_DOMAIN in the program's constants.rs
+const NOTE_ID_DOMAIN = new TextEncoder().encode('private_alpha_vault:note_id:v1');
+// Default period over which applied yield vests into the yield index
+const DEFAULT_YIELD_VESTING_SECS = 24 * 60 * 60;
 
 type InstructionName = 'initialize_vault' | 'deposit' | 'withdraw' | 'apply_yield' | 'transfer' | 'claim_access' | 'claim_yield' | 'create_stealth_note' | 'claim_stealth_note';
 
@@ -51,6 +58,14 @@ export interface VaultMetadataSummary {
   yieldIndex: string;
 }
 
+export interface YieldRecordInfo {
+  yieldIndexAfter: string; // decimal string (u128)
+  timestamp: number;
+  lamports: number; // net of the performance fee
+  feeLamports: number;
+  totalEscrowLamports: number;
+}
+
 export interface DecryptedBalance {
   value: bigint;
 }
@@ -158,7 +173,7 @@ class VaultService {
    * Apply yield to the vault (authority only)
    * @param amount Amount in SOL to add as yield
    */
-  async applyYield(amount: number): Promise<string> {
+  async applyYield(amount: number, vestingPeriodSecs: number = DEFAULT_YIELD_VESTING_SECS): Promise<string> {
     if (!this.publicKey || !this.wallet) {
       throw new Error('Connect a wallet before applying yield.');
     }
@@ -168,10 +183,12 @@ class VaultService {
     const encryptedBuffer = hexToBuffer(encryptedHex);
 
     const [vaultPda] = PublicKey.findProgramAddressSync([SEED_VAULT], PROGRAM_ID);
+    const [yieldHistoryPda] = PublicKey.findProgramAddressSync([SEED_YIELD_HISTORY], PROGRAM_ID);
 
     const payload = this.concatBytes(
       this.serializeVector(encryptedBuffer),
-      this.serializeU64(lamports)
+      this.serializeU64(lamports),
+      this.serializeU64(BigInt(vestingPeriodSecs)) // i64, non-negative
     );
     const instructionData = this.buildInstructionData('apply_yield', payload);
 
@@ -182,6 +199,7 @@ class VaultService {
         { pubkey: this.publicKey, isSigner: true, isWritable: false },
         { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
         { pubkey: INCO_LIGHTNING_ID, isSigner: false, isWritable: false },
+        { pubkey: yieldHistoryPda, isSigner: false, isWritable: true },
       ],
       data: instructionData,
     });
@@ -193,8 +211,9 @@ class VaultService {
    * Transfer SOL to another user within the vault
    * @param amount Amount in SOL to transfer
    * @param recipient Recipient's public key
+   * @param memo Optional memo, already encrypted to the recipient (max 256 bytes)
    */
-  async transfer(amount: number, recipient: PublicKey): Promise<string> {
+  async transfer(amount: number, recipient: PublicKey, memo?: Uint8Array): Promise<string> {
     if (!this.publicKey || !this.wallet) {
       throw new Error('Connect a wallet before transferring.');
     }
@@ -218,8 +237,8 @@ class VaultService {
     console.log(`[Vault] Sender PDA: ${senderPda.toBase58()}`);
     console.log(`[Vault] Recipient PDA: ${recipientPda.toBase58()}`);
 
-    const senderEscrowLamports = await this.connection.getBalance(senderPda);
-    if (senderEscrowLamports < Number(lamports)) {
+    const senderEscrowLamports = await this.fetchEscrowLamports(senderPda);
+    if (senderEscrowLamports < lamports) {
       throw new Error('Insufficient vault balance for this transfer.');
     }
 
@@ -227,7 +246,8 @@ class VaultService {
 
     const payload = this.concatBytes(
       this.serializeVector(encryptedBuffer),
-      this.serializeU64(lamports)
+      this.serializeU64(lamports),
+      this.serializeOptionalVector(memo)
     );
     const instructionData = this.buildInstructionData('transfer', payload);
 
@@ -357,7 +377,8 @@ class VaultService {
   }
 
   /**
-   * Fetch the user's escrowed SOL balance (lamports) held in the UserPosition PDA.
+   * Fetch the user's escrowed SOL balance, i.e. the principal recorded on the
+   * UserPosition (the SOL itself is pooled in the vault PDA).
    */
   async fetchUserEscrowBalance(): Promise<number> {
     if (!this.publicKey) {
@@ -368,8 +389,8 @@ class VaultService {
       PROGRAM_ID
     );
     try {
-      const lamports = await this.connection.getBalance(userPda);
-      return lamports / 1_000_000_000;
+      const lamports = await this.fetchEscrowLamports(userPda);
+      return Number(lamports) / 1_000_000_000;
     } catch (err) {
       if (err instanceof TypeError && err.message === 'Failed to fetch') {
         throw new Error('Network error: Unable to fetch escrow balance.');
@@ -381,6 +402,45 @@ class VaultService {
   /**
    * Fetch user's last_yield_index from their UserPosition account.
    */
+  /**
+   * Fetch past yield distributions, oldest first.
+   */
+  async fetchYieldHistory(): Promise<YieldRecordInfo[]> {
+    const [yieldHistoryPda] = PublicKey.findProgramAddressSync([SEED_YIELD_HISTORY], PROGRAM_ID);
+    const accountInfo = await this.connection.getAccountInfo(yieldHistoryPda);
+    if (!accountInfo) {
+      return [];
+    }
+    // Layout (zero-copy):
+    // 0..8 discriminator
+    // 8..16 head (u64 LE)
+    // 16..24 count (u64 LE)
+    // 24.. records, 48 bytes each:
+    //   0..16 yield_index_after (u128 LE), 16..24 timestamp (i64 LE),
+    //   24..32 lamports, 32..40 fee_lamports, 40..48 total_escrow_lamports (u64 LE)
+    const data = accountInfo.data;
+    const view = new DataView(data.buffer, data.byteOffset, data.byteLength);
+    const head = Number(view.getBigUint64(8, true));
+    const count = Number(view.getBigUint64(16, true));
+    const records: YieldRecordInfo[] = [];
+    for (let n = 0; n < count; n += 1) {
+      const slot = (head - count + n + YIELD_HISTORY_LEN) % YIELD_HISTORY_LEN;
+      const offset = 24 + slot * 48;
+      let yieldIndexAfter = 0n;
+      for (let i = 15; i >= 0; i--) {
+        yieldIndexAfter = yieldIndexAfter * 256n + BigInt(data[offset + i]!);
+      }
+      records.push({
+        yieldIndexAfter: yieldIndexAfter.toString(),
+        timestamp: Number(view.getBigInt64(offset + 16, true)),
+        lamports: Number(view.getBigUint64(offset + 24, true)),
+        feeLamports: Number(view.getBigUint64(offset + 32, true)),
+        totalEscrowLamports: Number(view.getBigUint64(offset + 40, true)),
+      });
+    }
+    return records;
+  }
+
   async fetchUserYieldIndex(): Promise<string> {
     if (!this.publicKey) {
       throw new Error('Connect a wallet before fetching yield index.');
@@ -399,6 +459,7 @@ class VaultService {
     // 40..56 encrypted_balance handle
     // 56..72 last_yield_index (u128 LE)
     // 72..73 bump
+    // 73..81 escrow_lamports (u64 LE)
     const data = accountInfo.data;
     const indexBytes = data.slice(56, 72);
     let yieldIndex = 0n;
@@ -488,11 +549,18 @@ class VaultService {
   }
 
   /**
-   * Generate a note ID from a secret passphrase using SHA256
+   * Generate a note ID from a secret passphrase.
+   * With a salt (current scheme, id_version 1):
+   *   SHA256(NOTE_ID_DOMAIN || program_id || vault_pda || salt || secret)
+   * Without one (notes created before salts were required, id_version 0): SHA256(secret)
    */
-  async generateNoteId(secret: string): Promise<Uint8Array> {
+  async generateNoteId(secret: string, salt?: Uint8Array | null): Promise<Uint8Array> {
     const encoder = new TextEncoder();
-    const data = encoder.encode(secret);
+    let data = encoder.encode(secret);
+    if (salt) {
+      const [vaultPda] = PublicKey.findProgramAddressSync([SEED_VAULT], PROGRAM_ID);
+      data = this.concatBytes(NOTE_ID_DOMAIN, PROGRAM_ID.toBytes(), vaultPda.toBytes(), salt, data);
+    }
     const hashBuffer = await crypto.subtle.digest('SHA-256', data);
     return new Uint8Array(hashBuffer);
   }
@@ -501,20 +569,27 @@ class VaultService {
    * Create a stealth note - send funds to a secret identifier instead of a public address.
    * The recipient can claim by knowing the secret passphrase.
    * @param amount Amount in SOL to send
-   * @param secret The secret passphrase (will be hashed to create note ID)
-   * @returns Transaction signature and the note ID (hex)
+   * @param secret The secret passphrase (salted and hashed to create note ID)
+   * @param memo Optional memo, already encrypted client-side (max 256 bytes)
+   * @returns Transaction signature, the note ID (hex) and the claim code to share
    */
-  async createStealthNote(amount: number, secret: string): Promise<{ signature: string; noteId: string }> {
+  async createStealthNote(
+    amount: number,
+    secret: string,
+    memo?: Uint8Array
+  ): Promise<{ signature: string; noteId: string; claimCode: string }> {
     if (!this.publicKey || !this.wallet) {
       throw new Error('Connect a wallet before creating a stealth note.');
     }
 
     console.log(`[Vault] Creating stealth note with ${amount} SOL...`);
 
-    // Generate note ID from secret
-    const noteId = await this.generateNoteId(secret);
+    // Generate a fresh salt and the salted note ID
+    const salt = crypto.getRandomValues(new Uint8Array(32));
+    const noteId = await this.generateNoteId(secret, salt);
     const noteIdHex = bytesToHex(noteId);
-    console.log(`[Vault] Note ID (hash of secret): ${noteIdHex}`);
+    const claimCode = formatClaimCode(salt, secret);
+    console.log(`[Vault] Note ID: ${noteIdHex}`);
 
     // Convert SOL to lamports and encrypt
     const lamports = BigInt(Math.round(amount * 1_000_000_000));
@@ -538,10 +613,13 @@ class VaultService {
     console.log(`[Vault] Stealth Note PDA: ${stealthNotePda.toBase58()}`);
 
     // Build instruction data: discriminator + note_id (32 bytes) + Vec<u8> encrypted + lamports (u64)
+    // + [u8; 32] salt + Option<Vec<u8>> memo
     const payload = this.concatBytes(
       noteId,
       this.serializeVector(encryptedBuffer),
-      this.serializeU64(lamports)
+      this.serializeU64(lamports),
+      salt,
+      this.serializeOptionalVector(memo)
     );
     const instructionData = this.buildInstructionData('create_stealth_note', payload);
 
@@ -559,27 +637,28 @@ class VaultService {
     console.log('[Vault] Sending create_stealth_note transaction...');
     const signature = await this.sendTransaction(ix);
     console.log(`[Vault] Stealth note created! Signature: ${signature}`);
-    console.log(`[Vault] Share this secret with the recipient: "${secret}"`);
+    console.log(`[Vault] Share this claim code with the recipient: "${claimCode}"`);
 
-    return { signature, noteId: noteIdHex };
+    return { signature, noteId: noteIdHex, claimCode };
   }
 
   /**
-   * Claim a stealth note by providing the secret passphrase.
-   * The secret is hashed and matched against the note ID.
-   * @param secret The secret passphrase shared by the sender
+   * Claim a stealth note by providing the claim code (or a legacy bare passphrase).
+   * The passphrase is checked on-chain against the note ID.
+   * @param claimCode The claim code shared by the sender
    */
-  async claimStealthNote(secret: string): Promise<string> {
+  async claimStealthNote(claimCode: string): Promise<string> {
     if (!this.publicKey || !this.wallet) {
       throw new Error('Connect a wallet before claiming a stealth note.');
     }
 
     console.log(`[Vault] Claiming stealth note with secret...`);
 
-    // Generate note ID from secret
-    const noteId = await this.generateNoteId(secret);
+    // Generate note ID from the claim code
+    const { salt, secret } = parseClaimCode(claimCode);
+    const noteId = await this.generateNoteId(secret, salt);
     const noteIdHex = bytesToHex(noteId);
-    console.log(`[Vault] Note ID (hash of secret): ${noteIdHex}`);
+    console.log(`[Vault] Note ID: ${noteIdHex}`);
 
     // Derive PDAs
     const [vaultPda] = PublicKey.findProgramAddressSync([SEED_VAULT], PROGRAM_ID);
@@ -602,7 +681,7 @@ class VaultService {
     }
 
     // Parse the note to check if claimed
-    const claimed = noteInfo.data[88] === 1; // claimed is at offset 88 (8+32+16+8+32+8+1-1)
+    const claimed = noteInfo.data[104] === 1; // claimed is at offset 104 (8+32+16+8+32+8)
     if (claimed) {
       throw new Error('This stealth note has already been claimed.');
     }
@@ -723,10 +802,11 @@ class VaultService {
   }
 
   /**
-   * Check if a stealth note exists for a given secret
+   * Check if a stealth note exists for a given claim code (or legacy passphrase)
    */
-  async checkStealthNote(secret: string): Promise<StealthNoteInfo | null> {
-    const noteId = await this.generateNoteId(secret);
+  async checkStealthNote(claimCode: string): Promise<StealthNoteInfo | null> {
+    const { salt, secret } = parseClaimCode(claimCode);
+    const noteId = await this.generateNoteId(secret, salt);
     const noteIdHex = bytesToHex(noteId);
     return this.fetchStealthNote(noteIdHex);
   }
@@ -805,8 +885,8 @@ class VaultService {
         throw new Error('Insufficient wallet SOL to deposit this amount.');
       }
     } else {
-      const escrowLamports = await this.connection.getBalance(userPda);
-      if (escrowLamports < Number(lamports)) {
+      const escrowLamports = await this.fetchEscrowLamports(userPda);
+      if (escrowLamports < lamports) {
         throw new Error('Insufficient vault balance for this withdrawal.');
       }
     }
@@ -906,6 +986,19 @@ class VaultService {
     return txSig;
   }
 
+  /**
+   * Read `escrow_lamports` from a UserPosition account (0 if it does not exist
+   * or still uses the pre-pool layout).
+   */
+  private async fetchEscrowLamports(userPda: PublicKey): Promise<bigint> {
+    const accountInfo = await this.connection.getAccountInfo(userPda);
+    if (!accountInfo || accountInfo.data.length < 81) {
+      return 0n;
+    }
+    const data = accountInfo.data;
+    return new DataView(data.buffer, data.byteOffset + 73, 8).getBigUint64(0, true);
+  }
+
   private buildInstructionData(
     name: InstructionName,
     payload?: Uint8Array
@@ -935,6 +1028,16 @@ class VaultService {
     return vector;
   }
 
+  /**
+   * Serialize an optional byte array as a Borsh Option<Vec<u8>>
+   */
+  private serializeOptionalVector(payload?: Uint8Array): Uint8Array {
+    if (!payload) {
+      return new Uint8Array([0]);
+    }
+    return this.concatBytes(new Uint8Array([1]), this.serializeVector(payload));
+  }
+
   /**
    * Serialize a bigint as u64 little-endian.
    */
@@ -1175,6 +1278,22 @@ class VaultService {
   }
 }
 
+/**
+ * Claim codes carry the note's salt alongside the passphrase: `<salt hex>:<passphrase>`.
+ * Anything else is treated as a legacy bare passphrase (unsalted note id).
+ */
+export function formatClaimCode(salt: Uint8Array, secret: string): string {
+  return `${bytesToHex(salt)}:${secret}`;
+}
+
+export function parseClaimCode(code: string): { salt: Uint8Array | null; secret: string } {
+  const match = /^([0-9a-f]{64}):(.*)$/s.exec(code);
+  if (!match) {
+    return { salt: null, secret: code };
+  }
+  return { salt: hexToBytes(match[1]), secret: match[2] };
+}
+
 function hexToBytes(hex: string): Uint8Array {
   const matches = hex.match(/.{1,2}/g);
   return new Uint8Array(